@@ -1,7 +1,11 @@
 use std::rc::Rc;
 
 use crate::{
-    expr::{Accept, Binary, Expr, Grouping, Literal, Unary, Visitor},
+    expr::{
+        Accept, Binary, Call, Compound, Conditional, Expr, Get, Grouping, Increment, Index,
+        Interpolation, Lambda, List, Literal, Logical, Map, Match, Pattern, Range, Set, SetIndex,
+        Super, This, Tuple, Unary, Visitor,
+    },
     literal_object::Literal as LiteralValue,
     token::Token,
 };
@@ -13,17 +17,165 @@ impl Visitor<String> for AstPrinter {
         self.parenthesize(&expr.operator().lexeme, &[expr.left(), expr.right()])
     }
 
+    fn visit_call_expr(&mut self, expr: &Call) -> String {
+        let mut exprs = vec![expr.callee()];
+        exprs.extend(expr.arguments());
+        let call = self.parenthesize("call", &exprs);
+        let named: Vec<String> = expr
+            .named()
+            .iter()
+            .map(|(name, value)| format!(" {}: {}", name.lexeme, self.print(value)))
+            .collect();
+        format!("{}{})", &call[..call.len() - 1], named.concat())
+    }
+
+    fn visit_compound_expr(&mut self, expr: &Compound) -> String {
+        self.parenthesize(&expr.operator().lexeme, &[expr.target(), expr.value()])
+    }
+
+    fn visit_conditional_expr(&mut self, expr: &Conditional) -> String {
+        self.parenthesize(
+            "?:",
+            &[expr.condition(), expr.then_branch(), expr.else_branch()],
+        )
+    }
+
+    fn visit_get_expr(&mut self, expr: &Get) -> String {
+        let operator = match expr.optional() {
+            true => "?.",
+            false => ".",
+        };
+        self.parenthesize(
+            &format!("{}{}", operator, expr.name().lexeme),
+            &[expr.object()],
+        )
+    }
+
     fn visit_grouping_expr(&mut self, expr: &Grouping) -> String {
         self.parenthesize("group", &[expr.expression()])
     }
 
+    fn visit_interpolation_expr(&mut self, expr: &Interpolation) -> String {
+        let parts: Vec<&Expr> = expr.parts().iter().collect();
+        self.parenthesize("interpolation", &parts)
+    }
+
+    fn visit_increment_expr(&mut self, expr: &Increment) -> String {
+        let name = match expr.prefix() {
+            true => format!("{}prefix", expr.operator().lexeme),
+            false => format!("{}postfix", expr.operator().lexeme),
+        };
+        self.parenthesize(&name, &[expr.target()])
+    }
+
+    fn visit_index_expr(&mut self, expr: &Index) -> String {
+        self.parenthesize("index", &[expr.object(), expr.index()])
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &Lambda) -> String {
+        let params: Vec<String> = expr
+            .params()
+            .iter()
+            .map(|param| match (param.variadic(), param.default()) {
+                (true, _) => format!("...{}", self.pattern(param.pattern())),
+                (false, Some(default)) => {
+                    format!("{}={}", self.pattern(param.pattern()), self.print(default))
+                }
+                (false, None) => self.pattern(param.pattern()),
+            })
+            .collect();
+        format!("(fun ({}))", params.join(" "))
+    }
+
+    fn visit_list_expr(&mut self, expr: &List) -> String {
+        let elements: Vec<&Expr> = expr.elements().iter().collect();
+        self.parenthesize("list", &elements)
+    }
+
+    fn visit_logical_expr(&mut self, expr: &Logical) -> String {
+        self.parenthesize(&expr.operator().lexeme, &[expr.left(), expr.right()])
+    }
+
     fn visit_literal_expr(&self, expr: &Literal) -> String {
         match expr.value() {
             LiteralValue::Null => String::from("null"),
             LiteralValue::Number(number) => format!("{}", number),
+            LiteralValue::Integer(integer) => format!("{}", integer),
+            LiteralValue::BigInt(big_int) => format!("{}", big_int),
+            LiteralValue::Function(function) => format!("{}", function),
+            LiteralValue::Enum(enum_) => format!("{}", enum_),
+            LiteralValue::EnumValue(value) => format!("{:?}", value),
+            LiteralValue::Generator(generator) => format!("{}", generator),
+            LiteralValue::Class(class) => format!("{}", class),
+            LiteralValue::Trait(trait_) => format!("{}", trait_),
+            LiteralValue::Native(native) => format!("{}", native),
+            LiteralValue::Instance(instance) => format!("{}", instance),
+            LiteralValue::Decimal(decimal) => format!("{}d", decimal),
             LiteralValue::String(string) => format!("{}", string),
             LiteralValue::Boolean(boolean) => format!("{}", boolean),
+            LiteralValue::List(list) => format!("{:?}", list.borrow()),
+            LiteralValue::Map(map) => format!("{:?}", map.borrow()),
+            LiteralValue::Tuple(tuple) => format!("{:?}", tuple),
+        }
+    }
+
+    fn visit_map_expr(&mut self, expr: &Map) -> String {
+        let mut exprs = Vec::new();
+        for (key, value) in expr.entries() {
+            exprs.push(key);
+            exprs.push(value);
+        }
+        self.parenthesize("map", &exprs)
+    }
+
+    fn visit_match_expr(&mut self, expr: &Match) -> String {
+        let mut arms = Vec::new();
+        for arm in expr.arms() {
+            let patterns: Vec<String> = arm
+                .patterns()
+                .iter()
+                .map(|pattern| self.pattern(pattern))
+                .collect();
+            let guard = match arm.guard() {
+                Some(guard) => format!(" if {}", self.print(guard)),
+                None => String::new(),
+            };
+            arms.push(format!(
+                " ({}{} => {})",
+                patterns.join(" | "),
+                guard,
+                self.print(arm.body())
+            ));
         }
+        format!("(match {}{})", self.print(expr.value()), arms.concat())
+    }
+
+    fn visit_range_expr(&mut self, expr: &Range) -> String {
+        self.parenthesize(&expr.operator().lexeme, &[expr.start(), expr.end()])
+    }
+
+    fn visit_set_expr(&mut self, expr: &Set) -> String {
+        self.parenthesize(
+            &format!("set .{}", expr.name().lexeme),
+            &[expr.object(), expr.value()],
+        )
+    }
+
+    fn visit_set_index_expr(&mut self, expr: &SetIndex) -> String {
+        self.parenthesize("set-index", &[expr.object(), expr.index(), expr.value()])
+    }
+
+    fn visit_super_expr(&mut self, expr: &Super) -> String {
+        format!("super.{}", expr.method().lexeme)
+    }
+
+    fn visit_this_expr(&mut self, _expr: &This) -> String {
+        String::from("this")
+    }
+
+    fn visit_tuple_expr(&mut self, expr: &Tuple) -> String {
+        let elements: Vec<&Expr> = expr.elements().iter().collect();
+        self.parenthesize("tuple", &elements)
     }
 
     fn visit_unary_expr(&mut self, expr: &Unary) -> String {
@@ -43,14 +195,84 @@ impl AstPrinter {
     pub fn print(&mut self, expr: &Expr) -> String {
         match expr {
             Expr::Binary(binary) => binary.accept(self),
+            Expr::Call(call) => call.accept(self),
+            Expr::Compound(compound) => compound.accept(self),
+            Expr::Conditional(conditional) => conditional.accept(self),
+            Expr::Get(get) => get.accept(self),
             Expr::Grouping(grouping) => grouping.accept(self),
+            Expr::Increment(increment) => increment.accept(self),
+            Expr::Index(index) => index.accept(self),
+            Expr::Interpolation(interpolation) => interpolation.accept(self),
+            Expr::Lambda(lambda) => lambda.accept(self),
+            Expr::List(list) => list.accept(self),
             Expr::Literal(literal) => literal.accept(self),
+            Expr::Logical(logical) => logical.accept(self),
+            Expr::Map(map) => map.accept(self),
+            Expr::Match(match_) => match_.accept(self),
+            Expr::Range(range) => range.accept(self),
+            Expr::Set(set) => set.accept(self),
+            Expr::SetIndex(set_index) => set_index.accept(self),
+            Expr::Super(super_) => super_.accept(self),
+            Expr::This(this) => this.accept(self),
+            Expr::Tuple(tuple) => tuple.accept(self),
             Expr::Unary(unary) => unary.accept(self),
             Expr::Variable(variable) => todo!(),
             Expr::Assign(assign) => todo!(),
         }
     }
 
+    fn pattern(&mut self, pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Name(name) => name.lexeme.clone(),
+            Pattern::Literal { value, .. } => self.print(value),
+            Pattern::Wildcard(_) => String::from("_"),
+            Pattern::Variant {
+                enum_name,
+                variant,
+                fields,
+            } => {
+                let name = format!("{}.{}", enum_name.lexeme, variant.lexeme);
+                match fields {
+                    Some(fields) => {
+                        let fields: Vec<String> =
+                            fields.iter().map(|field| self.pattern(field)).collect();
+                        format!("{}({})", name, fields.join(" "))
+                    }
+                    None => name,
+                }
+            }
+            Pattern::List { elements, rest, .. } => {
+                let mut parts: Vec<String> = elements
+                    .iter()
+                    .map(|element| self.pattern(element))
+                    .collect();
+                parts.extend(rest.iter().map(|rest| format!("...{}", rest.lexeme)));
+                format!("[{}]", parts.join(" "))
+            }
+            Pattern::Tuple { elements, .. } => {
+                let parts: Vec<String> = elements
+                    .iter()
+                    .map(|element| self.pattern(element))
+                    .collect();
+                format!("(tuple {})", parts.join(" "))
+            }
+            Pattern::Map { entries, .. } => {
+                let parts: Vec<String> = entries
+                    .iter()
+                    .map(|(key, pattern)| format!("{}: {}", key.lexeme, self.pattern(pattern)))
+                    .collect();
+                format!("{{{}}}", parts.join(" "))
+            }
+            Pattern::Instance { class, fields } => {
+                let parts: Vec<String> = fields
+                    .iter()
+                    .map(|(key, pattern)| format!("{}: {}", key.lexeme, self.pattern(pattern)))
+                    .collect();
+                format!("{}{{{}}}", class.lexeme, parts.join(" "))
+            }
+        }
+    }
+
     fn parenthesize(&mut self, name: &str, exprs: &[&Expr]) -> String {
         let mut builder = String::new();
 
@@ -72,6 +294,7 @@ pub fn print_ast() {
         "*".to_string(),
         LiteralValue::Null,
         1,
+        1,
     );
 
     let grouping = Grouping::new(Expr::Literal(Rc::new(Literal::new(LiteralValue::Number(
@@ -84,6 +307,7 @@ pub fn print_ast() {
             "-".to_string(),
             LiteralValue::Null,
             1,
+            1,
         ),
         Expr::Literal(Rc::new(Literal::new(LiteralValue::Number(123.0)))),
     );
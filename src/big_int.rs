@@ -0,0 +1,546 @@
+use std::{cmp::Ordering, fmt};
+
+// Limbs hold nine decimal digits each, which keeps parsing and printing
+// trivial and leaves room for a limb product in a u64.
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// An arbitrary-precision integer, used once a value no longer fits in an
+/// `i64`. Limbs are little-endian without trailing zeros, and zero is never
+/// negative, so the derived equality and hashing compare values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt {
+            negative: false,
+            limbs: vec![],
+        }
+    }
+
+    pub fn from_i64(value: i64) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = Vec::new();
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+        BigInt::new(value < 0, limbs)
+    }
+
+    /// Converts a float with no fractional part, None for anything else.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() || value.fract() != 0.0 {
+            return None;
+        }
+        // Formatting a whole float prints its exact digits.
+        BigInt::parse(&format!("{:.0}", value))
+    }
+
+    /// Parses an optional '-' followed by decimal digits.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut limbs = Vec::new();
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            limbs.push(digits[start..end].parse().ok()?);
+            end = start;
+        }
+        Some(BigInt::new(negative, limbs))
+    }
+
+    pub fn pow10(exponent: u32) -> Self {
+        let mut limbs = vec![0; exponent as usize / BASE_DIGITS];
+        limbs.push(10u32.pow(exponent % BASE_DIGITS as u32));
+        BigInt::new(false, limbs)
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let mut magnitude: u64 = 0;
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude.checked_mul(BASE)?.checked_add(*limb as u64)?;
+        }
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .limbs
+            .iter()
+            .rev()
+            .fold(0.0, |total, limb| total * BASE as f64 + *limb as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// The base-2 logarithm of the magnitude, roughly, for telling how
+    /// large a result would get before computing it. Zero gives negative
+    /// infinity.
+    pub fn log2(&self) -> f64 {
+        let top = self
+            .limbs
+            .iter()
+            .rev()
+            .take(2)
+            .fold(0.0, |total, limb| total * BASE as f64 + *limb as f64);
+        let rest = self.limbs.len().saturating_sub(2);
+        top.log2() + rest as f64 * (BASE as f64).log2()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb % 2 == 0)
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::new(false, self.limbs.clone())
+    }
+
+    pub fn neg(&self) -> Self {
+        BigInt::new(!self.negative, self.limbs.clone())
+    }
+
+    pub fn add(&self, other: &BigInt) -> Self {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_magnitudes(&self.limbs, &other.limbs));
+        }
+        match compare_magnitudes(&self.limbs, &other.limbs) {
+            Ordering::Less => {
+                BigInt::new(other.negative, sub_magnitudes(&other.limbs, &self.limbs))
+            }
+            _ => BigInt::new(self.negative, sub_magnitudes(&self.limbs, &other.limbs)),
+        }
+    }
+
+    pub fn sub(&self, other: &BigInt) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &BigInt) -> Self {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, left) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, right) in other.limbs.iter().enumerate() {
+                let current = limbs[i + j] + *left as u64 * *right as u64 + carry;
+                limbs[i + j] = current % BASE;
+                carry = current / BASE;
+            }
+            limbs[i + other.limbs.len()] += carry;
+        }
+        let limbs = limbs.into_iter().map(|limb| limb as u32).collect();
+        BigInt::new(self.negative != other.negative, limbs)
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = BigInt::from_i64(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.mul(&base);
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// Quotient truncated toward zero and the remainder, which takes the
+    /// sign of `self`. None when dividing by zero.
+    pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitudes(&self.limbs, &other.limbs);
+        Some((
+            BigInt::new(self.negative != other.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        ))
+    }
+
+    /// Bitwise operators treat values as two's complement with an infinite
+    /// sign extension, so they agree with the `i64` operators.
+    pub fn and(&self, other: &BigInt) -> Self {
+        self.bitwise(other, |left, right| left & right)
+    }
+
+    pub fn or(&self, other: &BigInt) -> Self {
+        self.bitwise(other, |left, right| left | right)
+    }
+
+    pub fn xor(&self, other: &BigInt) -> Self {
+        self.bitwise(other, |left, right| left ^ right)
+    }
+
+    pub fn not(&self) -> Self {
+        self.neg().sub(&BigInt::from_i64(1))
+    }
+
+    /// Shifts the magnitude left, which multiplies by `2^shift`.
+    pub fn shl(&self, shift: u32) -> Self {
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        let bits = shift % 32;
+        let mut words = vec![0; (shift / 32) as usize];
+        let mut carry = 0;
+        for word in self.magnitude_words() {
+            words.push(word << bits | carry);
+            carry = if bits == 0 { 0 } else { word >> (32 - bits) };
+        }
+        words.push(carry);
+        BigInt::from_magnitude_words(self.negative, &words)
+    }
+
+    /// Shifts right, rounding toward negative infinity like `i64 >>`.
+    pub fn shr(&self, shift: u32) -> Self {
+        let (quotient, remainder) = self
+            .div_rem(&BigInt::from_i64(2).pow(shift))
+            .expect("powers of two are never zero");
+        if remainder.is_negative() {
+            quotient.sub(&BigInt::from_i64(1))
+        } else {
+            quotient
+        }
+    }
+
+    fn bitwise(&self, other: &BigInt, op: fn(u32, u32) -> u32) -> Self {
+        let (left, right) = (self.to_words(), other.to_words());
+        // One extra word holds the sign of both operands.
+        let len = left.len().max(right.len()) + 1;
+        let extend = |words: &[u32], negative: bool| {
+            let fill = if negative { u32::MAX } else { 0 };
+            let mut words = words.to_vec();
+            words.resize(len, fill);
+            words
+        };
+        let left = extend(&left, self.negative);
+        let right = extend(&right, other.negative);
+        let words: Vec<u32> = left.iter().zip(&right).map(|(l, r)| op(*l, *r)).collect();
+        BigInt::from_words(&words)
+    }
+
+    // Little-endian 32-bit words of the value in two's complement, without
+    // the sign extension.
+    fn to_words(&self) -> Vec<u32> {
+        let mut words = self.magnitude_words();
+        if self.negative {
+            negate_words(&mut words);
+        }
+        words
+    }
+
+    // The inverse of `to_words`, taking the top bit as the sign.
+    fn from_words(words: &[u32]) -> Self {
+        let mut words = words.to_vec();
+        let negative = words.last().is_some_and(|word| word >> 31 == 1);
+        if negative {
+            negate_words(&mut words);
+        }
+        BigInt::from_magnitude_words(negative, &words)
+    }
+
+    // Little-endian 32-bit words of the magnitude.
+    fn magnitude_words(&self) -> Vec<u32> {
+        let word = BigInt::from_i64(1 << 32);
+        let mut magnitude = self.abs();
+        let mut words = Vec::new();
+        while !magnitude.is_zero() {
+            let (quotient, remainder) = magnitude.div_rem(&word).expect("word is not zero");
+            words.push(remainder.to_i64().expect("a word fits in an i64") as u32);
+            magnitude = quotient;
+        }
+        words
+    }
+
+    fn from_magnitude_words(negative: bool, words: &[u32]) -> Self {
+        let word = BigInt::from_i64(1 << 32);
+        let magnitude = words.iter().rev().fold(BigInt::zero(), |total, limb| {
+            total.mul(&word).add(&BigInt::from_i64(*limb as i64))
+        });
+        if negative {
+            magnitude.neg()
+        } else {
+            magnitude
+        }
+    }
+
+    fn new(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+}
+
+// Two's complement negation: invert every bit and add one.
+fn negate_words(words: &mut [u32]) {
+    let mut carry = true;
+    for word in words.iter_mut() {
+        (*word, carry) = (!*word).overflowing_add(carry as u32);
+    }
+}
+
+fn compare_magnitudes(left: &[u32], right: &[u32]) -> Ordering {
+    left.len()
+        .cmp(&right.len())
+        .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(left.len().max(right.len()) + 1);
+    let mut carry = 0;
+    for i in 0..left.len().max(right.len()) {
+        let sum = *left.get(i).unwrap_or(&0) as u64 + *right.get(i).unwrap_or(&0) as u64 + carry;
+        limbs.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+    limbs.push(carry as u32);
+    limbs
+}
+
+// Expects `left` to be at least as large as `right`.
+fn sub_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(left.len());
+    let mut borrow = 0;
+    for (i, limb) in left.iter().enumerate() {
+        let subtrahend = *right.get(i).unwrap_or(&0) as i64 + borrow;
+        let mut difference = *limb as i64 - subtrahend;
+        borrow = 0;
+        if difference < 0 {
+            difference += BASE as i64;
+            borrow = 1;
+        }
+        limbs.push(difference as u32);
+    }
+    limbs
+}
+
+fn mul_magnitude_small(limbs: &[u32], factor: u64) -> Vec<u32> {
+    let mut product = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for limb in limbs {
+        let current = *limb as u64 * factor + carry;
+        product.push((current % BASE) as u32);
+        carry = current / BASE;
+    }
+    while carry > 0 {
+        product.push((carry % BASE) as u32);
+        carry /= BASE;
+    }
+    trim(product)
+}
+
+// Schoolbook long division, one limb of the quotient at a time. Each limb is
+// found by binary search, which is slow for huge numbers but simple.
+fn div_rem_magnitudes(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0; dividend.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+
+    for i in (0..dividend.len()).rev() {
+        remainder.insert(0, dividend[i]);
+        remainder = trim(remainder);
+
+        let (mut low, mut high) = (0, BASE - 1);
+        while low < high {
+            let middle = (low + high).div_ceil(2);
+            let product = mul_magnitude_small(divisor, middle);
+            if compare_magnitudes(&product, &remainder) == Ordering::Greater {
+                high = middle - 1;
+            } else {
+                low = middle;
+            }
+        }
+
+        if low > 0 {
+            let product = mul_magnitude_small(divisor, low);
+            remainder = trim(sub_magnitudes(&remainder, &product));
+        }
+        quotient[i] = low as u32;
+    }
+
+    (quotient, remainder)
+}
+
+fn trim(mut limbs: Vec<u32>) -> Vec<u32> {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    limbs
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.limbs, &other.limbs),
+            (true, true) => compare_magnitudes(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((most, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", most)?;
+        for limb in rest.iter().rev() {
+            write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(text: &str) -> BigInt {
+        BigInt::parse(text).unwrap()
+    }
+
+    #[test]
+    fn add_and_sub_handle_signs() {
+        assert_eq!(big("5").add(&big("-7")), big("-2"));
+        assert_eq!(big("-5").add(&big("7")), big("2"));
+        assert_eq!(big("-5").add(&big("-7")), big("-12"));
+        assert_eq!(big("-5").sub(&big("-5")), BigInt::zero());
+        assert!(!big("-5").sub(&big("-5")).is_negative());
+        assert_eq!(
+            big("1000000000000000000").sub(&big("1")),
+            big("999999999999999999")
+        );
+        assert_eq!(
+            big("-999999999999999999").sub(&big("1")),
+            big("-1000000000000000000")
+        );
+    }
+
+    #[test]
+    fn div_rem_with_multi_limb_divisors() {
+        let dividend = big("123456789012345678901234567890123456789");
+        let divisor = big("98765432109876543210");
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(quotient, big("1249999988609375000"));
+        assert_eq!(remainder, big("15297067891529706789"));
+        assert_eq!(quotient.mul(&divisor).add(&remainder), dividend);
+
+        let (quotient, remainder) = dividend.neg().div_rem(&divisor).unwrap();
+        assert_eq!(quotient, big("-1249999988609375000"));
+        assert_eq!(remainder, big("-15297067891529706789"));
+
+        let (quotient, remainder) = divisor.div_rem(&dividend).unwrap();
+        assert_eq!(quotient, BigInt::zero());
+        assert_eq!(remainder, divisor);
+
+        assert_eq!(dividend.div_rem(&BigInt::zero()), None);
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(big("2").pow(100), big("1267650600228229401496703205376"));
+        assert_eq!(big("-3").pow(3), big("-27"));
+        assert_eq!(big("-3").pow(4), big("81"));
+        assert_eq!(big("12345").pow(0), big("1"));
+        assert_eq!(BigInt::zero().pow(5), BigInt::zero());
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(
+            BigInt::from_f64(2f64.powi(64)),
+            Some(big("18446744073709551616"))
+        );
+        assert_eq!(BigInt::from_f64(-1e20), Some(big("-100000000000000000000")));
+        assert_eq!(BigInt::from_f64(-0.0), Some(BigInt::zero()));
+        assert_eq!(BigInt::from_f64(0.5), None);
+        assert_eq!(BigInt::from_f64(f64::MIN_POSITIVE / 2.0), None);
+        assert_eq!(BigInt::from_f64(f64::INFINITY), None);
+        assert_eq!(BigInt::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn bitwise_matches_twos_complement() {
+        let x = big("9223372036854775808");
+        assert_eq!(x.and(&x.sub(&big("1"))), BigInt::zero());
+        assert_eq!(x.neg().and(&big("255")), BigInt::zero());
+        assert_eq!(
+            big("-18446744073709551616").or(&big("7")),
+            big("-18446744073709551609")
+        );
+        assert_eq!(big("-1").xor(&x), big("-9223372036854775809"));
+        assert_eq!(x.not(), big("-9223372036854775809"));
+        assert_eq!(x.shr(1), big("4611686018427387904"));
+        assert_eq!(big("-5").shr(1), big("-3"));
+        assert_eq!(x.neg().shr(100), big("-1"));
+        assert_eq!(big("3").shl(62), big("13835058055282163712"));
+    }
+
+    #[test]
+    fn shl_shifts_the_magnitude() {
+        assert_eq!(
+            big("123456789012345678901234567890").shl(33),
+            big("1060485742554393662255439366224483450880")
+        );
+        assert_eq!(big("-3").shl(100), big("-3802951800684688204490109616128"));
+        assert_eq!(big("-3").shl(100).shr(100), big("-3"));
+        assert_eq!(big("7").shl(0), big("7"));
+        assert_eq!(BigInt::zero().shl(u32::MAX), BigInt::zero());
+    }
+
+    #[test]
+    fn log2_estimates_the_magnitude() {
+        assert_eq!(BigInt::zero().log2(), f64::NEG_INFINITY);
+        assert_eq!(big("-1024").log2(), 10.0);
+        let estimate = big("1").shl(1000).log2();
+        assert!((estimate - 1000.0).abs() < 1e-6, "{}", estimate);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            big("-1000000000000000001").to_string(),
+            "-1000000000000000001"
+        );
+        assert_eq!(big("-0").to_string(), "0");
+    }
+}
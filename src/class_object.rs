@@ -0,0 +1,187 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use crate::{function_object::Function, literal_object::Literal};
+
+/// A `class` declaration at runtime. Members not found on a class are
+/// looked up on its superclass.
+#[derive(Debug)]
+pub struct Class {
+    name: String,
+    superclass: Option<Rc<Class>>,
+    // The traits named after `with`. Their methods are already mixed into
+    // `methods`, these are kept for `implements`.
+    traits: Vec<Rc<Trait>>,
+    methods: HashMap<String, Rc<Function>>,
+    getters: HashMap<String, Rc<Function>>,
+    setters: HashMap<String, Rc<Function>>,
+    statics: HashMap<String, Rc<Function>>,
+}
+
+/// The members of a class, keyed by name, as they are declared.
+#[derive(Default)]
+pub struct Members {
+    pub methods: HashMap<String, Rc<Function>>,
+    pub getters: HashMap<String, Rc<Function>>,
+    pub setters: HashMap<String, Rc<Function>>,
+    pub statics: HashMap<String, Rc<Function>>,
+}
+
+/// A `trait` declaration at runtime: methods that classes mix in, and the
+/// names of the ones they have to define themselves.
+#[derive(Debug)]
+pub struct Trait {
+    name: String,
+    methods: HashMap<String, Rc<Function>>,
+    required: Vec<String>,
+}
+
+/// An object made by calling a class. Instances are shared by reference,
+/// so changes to their fields are visible through every copy.
+pub struct Instance {
+    class: Rc<Class>,
+    fields: RefCell<HashMap<String, Literal>>,
+}
+
+impl Class {
+    pub fn new(
+        name: String,
+        superclass: Option<Rc<Class>>,
+        traits: Vec<Rc<Trait>>,
+        members: Members,
+    ) -> Self {
+        Class {
+            name,
+            superclass,
+            traits,
+            methods: members.methods,
+            getters: members.getters,
+            setters: members.setters,
+            statics: members.statics,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn traits(&self) -> &Vec<Rc<Trait>> {
+        &self.traits
+    }
+
+    pub fn method(&self, name: &str) -> Option<&Rc<Function>> {
+        self.find(name, |class| &class.methods)
+    }
+
+    pub fn getter(&self, name: &str) -> Option<&Rc<Function>> {
+        self.find(name, |class| &class.getters)
+    }
+
+    pub fn setter(&self, name: &str) -> Option<&Rc<Function>> {
+        self.find(name, |class| &class.setters)
+    }
+
+    pub fn static_method(&self, name: &str) -> Option<&Rc<Function>> {
+        self.find(name, |class| &class.statics)
+    }
+
+    /// Whether the class is `other` or inherits from it.
+    pub fn inherits(&self, other: &Class) -> bool {
+        std::ptr::eq(self, other)
+            || self
+                .superclass
+                .as_ref()
+                .is_some_and(|superclass| superclass.inherits(other))
+    }
+
+    /// Whether the class or one of its superclasses uses `trait_`.
+    pub fn implements(&self, trait_: &Rc<Trait>) -> bool {
+        self.traits.iter().any(|other| Rc::ptr_eq(other, trait_))
+            || self
+                .superclass
+                .as_ref()
+                .is_some_and(|superclass| superclass.implements(trait_))
+    }
+
+    fn find(
+        &self,
+        name: &str,
+        members: fn(&Class) -> &HashMap<String, Rc<Function>>,
+    ) -> Option<&Rc<Function>> {
+        match members(self).get(name) {
+            Some(function) => Some(function),
+            None => self.superclass.as_ref()?.find(name, members),
+        }
+    }
+}
+
+impl Trait {
+    pub fn new(
+        name: String,
+        methods: HashMap<String, Rc<Function>>,
+        required: Vec<String>,
+    ) -> Self {
+        Trait {
+            name,
+            methods,
+            required,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &HashMap<String, Rc<Function>> {
+        &self.methods
+    }
+
+    pub fn required(&self) -> &Vec<String> {
+        &self.required
+    }
+}
+
+impl Instance {
+    pub fn new(class: Rc<Class>) -> Self {
+        Instance {
+            class,
+            fields: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn class(&self) -> &Rc<Class> {
+        &self.class
+    }
+
+    pub fn field(&self, name: &str) -> Option<Literal> {
+        self.fields.borrow().get(name).cloned()
+    }
+
+    pub fn set_field(&self, name: &str, value: Literal) {
+        self.fields.borrow_mut().insert(name.to_string(), value);
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<class {}>", self.name)
+    }
+}
+
+impl fmt::Display for Trait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<trait {}>", self.name)
+    }
+}
+
+// Fields can hold the instance itself, so they are left out.
+impl fmt::Debug for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} instance>", self.class.name)
+    }
+}
@@ -0,0 +1,351 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::big_int::BigInt;
+
+/// Digits kept after the point when a division does not come out exact.
+pub const DIVISION_SCALE: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+    HalfEven,
+    HalfUp,
+    HalfDown,
+    Up,
+    Down,
+    Ceiling,
+    Floor,
+}
+
+/// An exact decimal number, `unscaled / 10^scale`. The scale is kept as
+/// written, so `12.50d` prints with two digits after the point.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+    unscaled: BigInt,
+    scale: u32,
+}
+
+impl RoundingMode {
+    pub fn from_name(name: &str) -> Option<RoundingMode> {
+        match name {
+            "half_even" => Some(RoundingMode::HalfEven),
+            "half_up" => Some(RoundingMode::HalfUp),
+            "half_down" => Some(RoundingMode::HalfDown),
+            "up" => Some(RoundingMode::Up),
+            "down" => Some(RoundingMode::Down),
+            "ceiling" => Some(RoundingMode::Ceiling),
+            "floor" => Some(RoundingMode::Floor),
+            _ => None,
+        }
+    }
+}
+
+impl Decimal {
+    pub fn new(unscaled: BigInt, scale: u32) -> Self {
+        Decimal { unscaled, scale }
+    }
+
+    /// The exact value of a finite float, which may take many digits.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = (bits & ((1 << 52) - 1)) as i64;
+        // value = mantissa * 2^exponent
+        let (mantissa, exponent) = match exponent {
+            0 => (fraction, -1074),
+            _ => (fraction | 1 << 52, exponent - 1075),
+        };
+
+        let mantissa = BigInt::from_i64(mantissa);
+        let decimal = if exponent >= 0 {
+            Decimal::new(mantissa.mul(&BigInt::from_i64(2).pow(exponent as u32)), 0)
+        } else {
+            // m * 2^-k is m * 5^k / 10^k
+            let k = (-exponent) as u32;
+            Decimal::new(mantissa.mul(&BigInt::from_i64(5).pow(k)), k)
+        };
+        let decimal = decimal.normalized();
+        Some(if value.is_sign_negative() {
+            decimal.neg()
+        } else {
+            decimal
+        })
+    }
+
+    /// Parses digits with an optional fractional part, like "12.50".
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.split_once('.') {
+            Some((whole, fraction)) => Some(Decimal::new(
+                BigInt::parse(&format!("{}{}", whole, fraction))?,
+                fraction.len() as u32,
+            )),
+            None => Some(Decimal::new(BigInt::parse(text)?, 0)),
+        }
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// The digits of the value without the decimal point.
+    pub fn unscaled(&self) -> &BigInt {
+        &self.unscaled
+    }
+
+    pub fn neg(&self) -> Self {
+        Decimal::new(self.unscaled.neg(), self.scale)
+    }
+
+    pub fn add(&self, other: &Decimal) -> Self {
+        let scale = self.scale.max(other.scale);
+        Decimal::new(self.rescaled(scale).add(&other.rescaled(scale)), scale)
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Decimal) -> Self {
+        Decimal::new(self.unscaled.mul(&other.unscaled), self.scale + other.scale)
+    }
+
+    /// None when the scale of the result would not fit.
+    pub fn pow(&self, exponent: u32) -> Option<Self> {
+        Some(Decimal::new(
+            self.unscaled.pow(exponent),
+            self.scale.checked_mul(exponent)?,
+        ))
+    }
+
+    /// Divides to `DIVISION_SCALE` digits, rounding half to even, then drops
+    /// trailing zeros down to the larger scale of the operands.
+    pub fn div(&self, other: &Decimal) -> Option<Self> {
+        let scale = DIVISION_SCALE.max(self.scale).max(other.scale);
+        let quotient = self.div_to_scale(other, scale, RoundingMode::HalfEven)?;
+        Some(quotient.trimmed(self.scale.max(other.scale)))
+    }
+
+    /// The quotient truncated toward zero, as a whole decimal.
+    pub fn div_trunc(&self, other: &Decimal) -> Option<Self> {
+        self.div_to_scale(other, 0, RoundingMode::Down)
+    }
+
+    /// The remainder of `div_trunc`, which takes the sign of `self`.
+    pub fn rem(&self, other: &Decimal) -> Option<Self> {
+        Some(self.sub(&self.div_trunc(other)?.mul(other)))
+    }
+
+    pub fn round(&self, scale: u32, mode: RoundingMode) -> Self {
+        if scale >= self.scale {
+            return Decimal::new(self.rescaled(scale), scale);
+        }
+        let divisor = BigInt::pow10(self.scale - scale);
+        let (quotient, remainder) = self
+            .unscaled
+            .div_rem(&divisor)
+            .expect("powers of ten are never zero");
+        Decimal::new(round_quotient(quotient, &remainder, &divisor, mode), scale)
+    }
+
+    /// The value as an integer, if it has no fractional part.
+    pub fn to_big_int(&self) -> Option<BigInt> {
+        let trimmed = self.trimmed(0);
+        (trimmed.scale == 0).then_some(trimmed.unscaled)
+    }
+
+    /// The same value without trailing zeros, so equal values are identical.
+    pub fn normalized(&self) -> Self {
+        self.trimmed(0)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    fn div_to_scale(&self, other: &Decimal, scale: u32, mode: RoundingMode) -> Option<Self> {
+        // self / other = (a / 10^sa) / (b / 10^sb) = a * 10^sb / (b * 10^sa)
+        let numerator = self.unscaled.mul(&BigInt::pow10(other.scale + scale));
+        let denominator = other.unscaled.mul(&BigInt::pow10(self.scale));
+        let (quotient, remainder) = numerator.div_rem(&denominator)?;
+        Some(Decimal::new(
+            round_quotient(quotient, &remainder, &denominator, mode),
+            scale,
+        ))
+    }
+
+    fn rescaled(&self, scale: u32) -> BigInt {
+        self.unscaled.mul(&BigInt::pow10(scale - self.scale))
+    }
+
+    fn trimmed(&self, min_scale: u32) -> Self {
+        let ten = BigInt::from_i64(10);
+        let mut decimal = self.clone();
+        while decimal.scale > min_scale {
+            match decimal.unscaled.div_rem(&ten) {
+                Some((quotient, remainder)) if remainder.is_zero() => {
+                    decimal = Decimal::new(quotient, decimal.scale - 1);
+                }
+                _ => break,
+            }
+        }
+        decimal
+    }
+}
+
+// Rounds a truncated `quotient` using what was left over from the division.
+fn round_quotient(
+    quotient: BigInt,
+    remainder: &BigInt,
+    divisor: &BigInt,
+    mode: RoundingMode,
+) -> BigInt {
+    if remainder.is_zero() {
+        return quotient;
+    }
+    let negative = remainder.is_negative() != divisor.is_negative();
+    let half = remainder
+        .abs()
+        .mul(&BigInt::from_i64(2))
+        .cmp(&divisor.abs());
+
+    let away_from_zero = match mode {
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => !negative,
+        RoundingMode::Floor => negative,
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfDown => half == Ordering::Greater,
+        RoundingMode::HalfEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && !quotient.is_even())
+        }
+    };
+
+    match (away_from_zero, negative) {
+        (false, _) => quotient,
+        (true, false) => quotient.add(&BigInt::from_i64(1)),
+        (true, true) => quotient.sub(&BigInt::from_i64(1)),
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        self.rescaled(scale).cmp(&other.rescaled(scale))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.unscaled.abs().to_string();
+        let scale = self.scale as usize;
+        if self.unscaled.is_negative() {
+            write!(f, "-")?;
+        }
+        if scale == 0 {
+            return write!(f, "{}", digits);
+        }
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}.{}", whole, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(text: &str) -> Decimal {
+        match text.strip_prefix('-') {
+            Some(text) => Decimal::parse(text).unwrap().neg(),
+            None => Decimal::parse(text).unwrap(),
+        }
+    }
+
+    fn round(text: &str, mode: RoundingMode) -> String {
+        decimal(text).round(0, mode).to_string()
+    }
+
+    #[test]
+    fn rounding_modes_at_exact_ties() {
+        use RoundingMode::*;
+        let cases = [
+            (HalfEven, ["2", "2", "-2", "-2"]),
+            (HalfUp, ["3", "2", "-3", "-2"]),
+            (HalfDown, ["2", "1", "-2", "-1"]),
+            (Up, ["3", "2", "-3", "-2"]),
+            (Down, ["2", "1", "-2", "-1"]),
+            (Ceiling, ["3", "2", "-2", "-1"]),
+            (Floor, ["2", "1", "-3", "-2"]),
+        ];
+        for (mode, expected) in cases {
+            let actual = ["2.5", "1.5", "-2.5", "-1.5"].map(|text| round(text, mode));
+            assert_eq!(actual, expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn add_and_sub_handle_signs() {
+        assert_eq!(decimal("1.25").add(&decimal("-2.5")).to_string(), "-1.25");
+        assert_eq!(decimal("-1.25").sub(&decimal("-2.5")).to_string(), "1.25");
+        assert_eq!(decimal("-0.1").sub(&decimal("0.2")).to_string(), "-0.3");
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(decimal("1.5").pow(3).unwrap().to_string(), "3.375");
+        assert_eq!(decimal("-0.1").pow(2).unwrap().to_string(), "0.01");
+        assert_eq!(decimal("2.5").pow(0).unwrap().to_string(), "1");
+        assert_eq!(decimal("0.01").pow(u32::MAX), None);
+    }
+
+    #[test]
+    fn from_f64_is_exact() {
+        assert_eq!(Decimal::from_f64(0.5).unwrap().to_string(), "0.5");
+        assert_eq!(
+            Decimal::from_f64(0.1).unwrap().to_string(),
+            "0.1000000000000000055511151231257827021181583404541015625"
+        );
+        assert_eq!(Decimal::from_f64(-2.0).unwrap().to_string(), "-2");
+
+        let smallest = Decimal::from_f64(f64::from_bits(1)).unwrap();
+        assert_eq!(smallest.scale(), 1074);
+        assert!(smallest
+            .to_string()
+            .ends_with("4565229087538682506419718265533447265625"));
+        let subnormal = Decimal::from_f64(f64::MIN_POSITIVE / 2.0).unwrap();
+        assert_eq!(
+            subnormal.mul(&decimal("2")),
+            Decimal::from_f64(f64::MIN_POSITIVE).unwrap()
+        );
+
+        assert!(Decimal::from_f64(f64::NAN).is_none());
+        assert!(Decimal::from_f64(f64::NEG_INFINITY).is_none());
+    }
+
+    #[test]
+    fn display_keeps_leading_fractional_zeros() {
+        assert_eq!(decimal("0.05").to_string(), "0.05");
+        assert_eq!(decimal("-0.005").to_string(), "-0.005");
+        assert_eq!(decimal("12.50").to_string(), "12.50");
+        assert_eq!(
+            decimal("1").div(&decimal("1000")).unwrap().to_string(),
+            "0.001"
+        );
+    }
+}
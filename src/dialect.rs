@@ -0,0 +1,118 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::token_type::TokenType;
+
+/// Keywords added on top of Lox. They are opt-in so that legacy scripts can
+/// keep using these words as identifiers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Extension {
+    Break = 1,
+    Continue = 1 << 1,
+    Let = 1 << 2,
+    Const = 1 << 3,
+    Match = 1 << 4,
+    Import = 1 << 5,
+    Enum = 1 << 6,
+    Yield = 1 << 7,
+    Trait = 1 << 8,
+}
+
+/// The language options the scanner runs with. The default is plain Lox.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dialect {
+    extensions: u16,
+}
+
+impl Extension {
+    pub const ALL: [Extension; 9] = [
+        Extension::Break,
+        Extension::Continue,
+        Extension::Let,
+        Extension::Const,
+        Extension::Match,
+        Extension::Import,
+        Extension::Enum,
+        Extension::Yield,
+        Extension::Trait,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Extension> {
+        Extension::ALL
+            .into_iter()
+            .find(|extension| extension.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Extension::Break => "break",
+            Extension::Continue => "continue",
+            Extension::Let => "let",
+            Extension::Const => "const",
+            Extension::Match => "match",
+            Extension::Import => "import",
+            Extension::Enum => "enum",
+            Extension::Yield => "yield",
+            Extension::Trait => "trait",
+        }
+    }
+}
+
+impl Dialect {
+    pub fn enable(&mut self, extension: Extension) {
+        self.extensions |= extension as u16;
+    }
+
+    pub fn is_enabled(&self, extension: Extension) -> bool {
+        self.extensions & extension as u16 != 0
+    }
+
+    pub fn keyword(&self, text: &str) -> Option<TokenType> {
+        match keywords().get(text) {
+            Some((type_, None)) => Some(*type_),
+            Some((type_, Some(extension))) if self.is_enabled(*extension) => Some(*type_),
+            _ => None,
+        }
+    }
+}
+
+// Built once and shared by every scanner, the REPL creates one per line.
+fn keywords() -> &'static HashMap<&'static str, (TokenType, Option<Extension>)> {
+    static KEYWORDS: OnceLock<HashMap<&'static str, (TokenType, Option<Extension>)>> =
+        OnceLock::new();
+
+    KEYWORDS.get_or_init(|| {
+        let mut keywords = HashMap::new();
+        keywords.insert("and", (TokenType::AND, None));
+        keywords.insert("class", (TokenType::CLASS, None));
+        keywords.insert("else", (TokenType::ELSE, None));
+        keywords.insert("false", (TokenType::FALSE, None));
+        keywords.insert("for", (TokenType::FOR, None));
+        keywords.insert("fun", (TokenType::FUN, None));
+        keywords.insert("if", (TokenType::IF, None));
+        keywords.insert("nil", (TokenType::NIL, None));
+        keywords.insert("or", (TokenType::OR, None));
+        keywords.insert("print", (TokenType::PRINT, None));
+        keywords.insert("return", (TokenType::RETURN, None));
+        keywords.insert("super", (TokenType::SUPER, None));
+        keywords.insert("this", (TokenType::THIS, None));
+        keywords.insert("true", (TokenType::TRUE, None));
+        keywords.insert("var", (TokenType::VAR, None));
+        keywords.insert("while", (TokenType::WHILE, None));
+
+        for extension in Extension::ALL {
+            let type_ = match extension {
+                Extension::Break => TokenType::BREAK,
+                Extension::Continue => TokenType::CONTINUE,
+                Extension::Let => TokenType::LET,
+                Extension::Const => TokenType::CONST,
+                Extension::Match => TokenType::MATCH,
+                Extension::Import => TokenType::IMPORT,
+                Extension::Enum => TokenType::ENUM,
+                Extension::Yield => TokenType::YIELD,
+                Extension::Trait => TokenType::TRAIT,
+            };
+            keywords.insert(extension.name(), (type_, Some(extension)));
+        }
+        keywords
+    })
+}
@@ -0,0 +1,91 @@
+use std::{fmt, rc::Rc};
+
+use crate::literal_object::Literal;
+
+/// An `enum` declaration at runtime, the type its values belong to.
+#[derive(Debug)]
+pub struct Enum {
+    name: String,
+    // Each variant's name with the names of the values it carries.
+    variants: Vec<(String, Vec<String>)>,
+}
+
+/// One variant of an enum together with the values it was built from.
+/// Values compare by their enum, variant and values.
+#[derive(Debug)]
+pub struct EnumValue {
+    enum_: Rc<Enum>,
+    variant: usize,
+    values: Vec<Literal>,
+}
+
+impl Enum {
+    pub fn new(name: String, variants: Vec<(String, Vec<String>)>) -> Self {
+        Enum { name, variants }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The position of the variant called `name`, if there is one.
+    pub fn variant(&self, name: &str) -> Option<usize> {
+        self.variants
+            .iter()
+            .position(|(variant, _)| variant == name)
+    }
+
+    pub fn fields(&self, variant: usize) -> &Vec<String> {
+        &self.variants[variant].1
+    }
+}
+
+impl EnumValue {
+    pub fn new(enum_: Rc<Enum>, variant: usize, values: Vec<Literal>) -> Self {
+        EnumValue {
+            enum_,
+            variant,
+            values,
+        }
+    }
+
+    pub fn enum_(&self) -> &Rc<Enum> {
+        &self.enum_
+    }
+
+    pub fn variant(&self) -> usize {
+        self.variant
+    }
+
+    pub fn variant_name(&self) -> &str {
+        &self.enum_.variants[self.variant].0
+    }
+
+    pub fn values(&self) -> &Vec<Literal> {
+        &self.values
+    }
+
+    /// The value carried under the field called `name`.
+    pub fn field(&self, name: &str) -> Option<&Literal> {
+        let index = self
+            .enum_
+            .fields(self.variant)
+            .iter()
+            .position(|field| field == name)?;
+        self.values.get(index)
+    }
+}
+
+impl PartialEq for EnumValue {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.enum_, &other.enum_)
+            && self.variant == other.variant
+            && self.values == other.values
+    }
+}
+
+impl fmt::Display for Enum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<enum {}>", self.name)
+    }
+}
@@ -2,10 +2,21 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{interpreter::RuntimeError, literal_object::Literal, token::Token};
 
+/// How a name was declared, which decides whether it can be assigned or
+/// declared again in the same scope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binding {
+    Var,
+    Let,
+    Const,
+}
+
 #[derive(Clone)]
 pub struct Environment {
     pub values: HashMap<String, Literal>,
     pub enclosing: Option<Rc<RefCell<Environment>>>,
+    // Names declared with `let` or `const`. Anything else is a `var`.
+    bindings: HashMap<String, Binding>,
 }
 
 impl Environment {
@@ -13,6 +24,7 @@ impl Environment {
         Environment {
             values: HashMap::new(),
             enclosing,
+            bindings: HashMap::new(),
         }
     }
 
@@ -20,6 +32,7 @@ impl Environment {
         Environment {
             values: HashMap::new(),
             enclosing: Some(enclosing),
+            bindings: HashMap::new(),
         }
     }
 
@@ -27,7 +40,38 @@ impl Environment {
         self.values.insert(name.to_string(), value);
     }
 
+    /// Like `define`, but fails where the parser can't tell, such as a name
+    /// declared again on a later REPL line.
+    pub fn declare(
+        &mut self,
+        name: &Token,
+        value: Literal,
+        binding: Binding,
+    ) -> Result<(), RuntimeError> {
+        let redeclared = self.values.contains_key(&name.lexeme)
+            && (binding != Binding::Var || self.bindings.contains_key(&name.lexeme));
+        if redeclared {
+            return Err(RuntimeError::new(
+                name.clone(),
+                &format!("Already a variable named '{}' in this scope.", name.lexeme),
+            ));
+        }
+
+        self.define(&name.lexeme, value);
+        if binding != Binding::Var {
+            self.bindings.insert(name.lexeme.clone(), binding);
+        }
+        Ok(())
+    }
+
     pub fn assign(&mut self, name: Token, value: Literal) -> Result<(), RuntimeError> {
+        if self.bindings.get(&name.lexeme) == Some(&Binding::Const) {
+            return Err(RuntimeError::new(
+                name.clone(),
+                &format!("Can't assign to constant '{}'.", name.lexeme),
+            ));
+        }
+
         if self.values.contains_key(&name.lexeme) {
             self.values.insert(name.lexeme.clone(), value);
             return Ok(());
@@ -1,13 +1,31 @@
 use std::rc::Rc;
 
-use crate::{literal_object::Literal as LiteralValue, token::Token};
+use crate::{literal_object::Literal as LiteralValue, stmt::Stmt, token::Token};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Expr {
     Assign(Rc<Assign>),
     Binary(Rc<Binary>),
+    Call(Rc<Call>),
+    Compound(Rc<Compound>),
+    Conditional(Rc<Conditional>),
+    Get(Rc<Get>),
     Grouping(Rc<Grouping>),
+    Increment(Rc<Increment>),
+    Index(Rc<Index>),
+    Interpolation(Rc<Interpolation>),
+    Lambda(Rc<Lambda>),
+    List(Rc<List>),
     Literal(Rc<Literal>),
+    Logical(Rc<Logical>),
+    Map(Rc<Map>),
+    Match(Rc<Match>),
+    Range(Rc<Range>),
+    Set(Rc<Set>),
+    SetIndex(Rc<SetIndex>),
+    Super(Rc<Super>),
+    This(Rc<This>),
+    Tuple(Rc<Tuple>),
     Unary(Rc<Unary>),
     Variable(Rc<Variable>),
 }
@@ -19,8 +37,26 @@ pub trait Accept {
 pub trait Visitor<R> {
     fn visit_assign_expr(&mut self, expr: &Assign) -> R;
     fn visit_binary_expr(&mut self, expr: &Binary) -> R;
+    fn visit_call_expr(&mut self, expr: &Call) -> R;
+    fn visit_compound_expr(&mut self, expr: &Compound) -> R;
+    fn visit_conditional_expr(&mut self, expr: &Conditional) -> R;
+    fn visit_get_expr(&mut self, expr: &Get) -> R;
     fn visit_grouping_expr(&mut self, expr: &Grouping) -> R;
+    fn visit_increment_expr(&mut self, expr: &Increment) -> R;
+    fn visit_index_expr(&mut self, expr: &Index) -> R;
+    fn visit_interpolation_expr(&mut self, expr: &Interpolation) -> R;
+    fn visit_lambda_expr(&mut self, expr: &Lambda) -> R;
+    fn visit_list_expr(&mut self, expr: &List) -> R;
     fn visit_literal_expr(&self, expr: &Literal) -> R;
+    fn visit_logical_expr(&mut self, expr: &Logical) -> R;
+    fn visit_map_expr(&mut self, expr: &Map) -> R;
+    fn visit_match_expr(&mut self, expr: &Match) -> R;
+    fn visit_range_expr(&mut self, expr: &Range) -> R;
+    fn visit_set_expr(&mut self, expr: &Set) -> R;
+    fn visit_set_index_expr(&mut self, expr: &SetIndex) -> R;
+    fn visit_super_expr(&mut self, expr: &Super) -> R;
+    fn visit_this_expr(&mut self, expr: &This) -> R;
+    fn visit_tuple_expr(&mut self, expr: &Tuple) -> R;
     fn visit_unary_expr(&mut self, expr: &Unary) -> R;
     fn visit_variable_expr(&mut self, expr: &Variable) -> R;
 }
@@ -38,16 +74,138 @@ pub struct Binary {
     right: Expr,
 }
 
+#[derive(Debug)]
+pub struct Call {
+    callee: Expr,
+    paren: Token,
+    arguments: Vec<Expr>,
+    // Arguments passed as `name: value`, which follow the positional ones.
+    named: Vec<(Token, Expr)>,
+}
+
+#[derive(Debug)]
+pub struct Compound {
+    target: Expr,
+    operator: Token,
+    value: Expr,
+}
+
+#[derive(Debug)]
+pub struct Conditional {
+    condition: Expr,
+    then_branch: Expr,
+    else_branch: Expr,
+}
+
+#[derive(Debug)]
+pub struct Get {
+    object: Expr,
+    name: Token,
+    // Set for `?.`, which yields nil instead of failing on a nil object.
+    optional: bool,
+}
+
 #[derive(Debug)]
 pub struct Grouping {
     expression: Expr,
 }
 
+#[derive(Debug)]
+pub struct Increment {
+    target: Expr,
+    operator: Token,
+    prefix: bool,
+}
+
+#[derive(Debug)]
+pub struct Index {
+    object: Expr,
+    bracket: Token,
+    index: Expr,
+}
+
+#[derive(Debug)]
+pub struct Interpolation {
+    // The start of the string, up to the first `${`.
+    start: Token,
+    parts: Vec<Expr>,
+}
+
+#[derive(Debug)]
+pub struct Lambda {
+    keyword: Token,
+    params: Vec<Parameter>,
+    body: Vec<Stmt>,
+}
+
+#[derive(Debug)]
+pub struct List {
+    elements: Vec<Expr>,
+}
+
 #[derive(Debug)]
 pub struct Literal {
     value: LiteralValue,
 }
 
+#[derive(Debug)]
+pub struct Logical {
+    left: Expr,
+    operator: Token,
+    right: Expr,
+}
+
+#[derive(Debug)]
+pub struct Map {
+    brace: Token,
+    entries: Vec<(Expr, Expr)>,
+}
+
+#[derive(Debug)]
+pub struct Match {
+    keyword: Token,
+    value: Expr,
+    arms: Vec<MatchArm>,
+}
+
+#[derive(Debug)]
+pub struct Range {
+    start: Expr,
+    operator: Token,
+    end: Expr,
+}
+
+#[derive(Debug)]
+pub struct Set {
+    object: Expr,
+    name: Token,
+    value: Expr,
+}
+
+#[derive(Debug)]
+pub struct SetIndex {
+    object: Expr,
+    bracket: Token,
+    index: Expr,
+    value: Expr,
+}
+
+#[derive(Debug)]
+pub struct Super {
+    keyword: Token,
+    method: Token,
+}
+
+#[derive(Debug)]
+pub struct This {
+    keyword: Token,
+}
+
+#[derive(Debug)]
+pub struct Tuple {
+    elements: Vec<Expr>,
+}
+
 #[derive(Debug)]
 pub struct Unary {
     operator: Token,
@@ -71,18 +229,126 @@ impl Accept for Binary {
     }
 }
 
+impl Accept for Call {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_call_expr(self)
+    }
+}
+
+impl Accept for Compound {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_compound_expr(self)
+    }
+}
+
+impl Accept for Conditional {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_conditional_expr(self)
+    }
+}
+
+impl Accept for Get {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_get_expr(self)
+    }
+}
+
 impl Accept for Grouping {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_grouping_expr(self)
     }
 }
 
+impl Accept for Increment {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_increment_expr(self)
+    }
+}
+
+impl Accept for Index {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_index_expr(self)
+    }
+}
+
+impl Accept for Interpolation {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_interpolation_expr(self)
+    }
+}
+
+impl Accept for Lambda {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_lambda_expr(self)
+    }
+}
+
+impl Accept for List {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_list_expr(self)
+    }
+}
+
 impl Accept for Literal {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_literal_expr(self)
     }
 }
 
+impl Accept for Logical {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_logical_expr(self)
+    }
+}
+
+impl Accept for Map {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_map_expr(self)
+    }
+}
+
+impl Accept for Match {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_match_expr(self)
+    }
+}
+
+impl Accept for Range {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_range_expr(self)
+    }
+}
+
+impl Accept for Set {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_set_expr(self)
+    }
+}
+
+impl Accept for SetIndex {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_set_index_expr(self)
+    }
+}
+
+impl Accept for Super {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_super_expr(self)
+    }
+}
+
+impl Accept for This {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_this_expr(self)
+    }
+}
+
+impl Accept for Tuple {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_tuple_expr(self)
+    }
+}
+
 impl Accept for Unary {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_unary_expr(self)
@@ -131,6 +397,104 @@ impl Binary {
     }
 }
 
+impl Call {
+    pub fn new(
+        callee: Expr,
+        paren: Token,
+        arguments: Vec<Expr>,
+        named: Vec<(Token, Expr)>,
+    ) -> Self {
+        Self {
+            callee,
+            paren,
+            arguments,
+            named,
+        }
+    }
+
+    pub fn callee(&self) -> &Expr {
+        &self.callee
+    }
+
+    pub fn paren(&self) -> &Token {
+        &self.paren
+    }
+
+    pub fn arguments(&self) -> &Vec<Expr> {
+        &self.arguments
+    }
+
+    pub fn named(&self) -> &Vec<(Token, Expr)> {
+        &self.named
+    }
+}
+
+impl Compound {
+    pub fn new(target: Expr, operator: Token, value: Expr) -> Self {
+        Self {
+            target,
+            operator,
+            value,
+        }
+    }
+
+    pub fn target(&self) -> &Expr {
+        &self.target
+    }
+
+    pub fn operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn value(&self) -> &Expr {
+        &self.value
+    }
+}
+
+impl Conditional {
+    pub fn new(condition: Expr, then_branch: Expr, else_branch: Expr) -> Self {
+        Self {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+
+    pub fn condition(&self) -> &Expr {
+        &self.condition
+    }
+
+    pub fn then_branch(&self) -> &Expr {
+        &self.then_branch
+    }
+
+    pub fn else_branch(&self) -> &Expr {
+        &self.else_branch
+    }
+}
+
+impl Get {
+    pub fn new(object: Expr, name: Token, optional: bool) -> Self {
+        Self {
+            object,
+            name,
+            optional,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn optional(&self) -> bool {
+        self.optional
+    }
+}
+
 impl Grouping {
     pub fn new(expr: Expr) -> Self {
         Self { expression: expr }
@@ -141,6 +505,283 @@ impl Grouping {
     }
 }
 
+impl Increment {
+    pub fn new(target: Expr, operator: Token, prefix: bool) -> Self {
+        Self {
+            target,
+            operator,
+            prefix,
+        }
+    }
+
+    pub fn target(&self) -> &Expr {
+        &self.target
+    }
+
+    pub fn operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn prefix(&self) -> bool {
+        self.prefix
+    }
+}
+
+impl Index {
+    pub fn new(object: Expr, bracket: Token, index: Expr) -> Self {
+        Self {
+            object,
+            bracket,
+            index,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn index(&self) -> &Expr {
+        &self.index
+    }
+}
+
+impl Interpolation {
+    pub fn new(start: Token, parts: Vec<Expr>) -> Self {
+        Self { start, parts }
+    }
+
+    pub fn start(&self) -> &Token {
+        &self.start
+    }
+
+    pub fn parts(&self) -> &Vec<Expr> {
+        &self.parts
+    }
+}
+
+impl Lambda {
+    pub fn new(keyword: Token, params: Vec<Parameter>, body: Vec<Stmt>) -> Self {
+        Self {
+            keyword,
+            params,
+            body,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn params(&self) -> &Vec<Parameter> {
+        &self.params
+    }
+
+    pub fn body(&self) -> &Vec<Stmt> {
+        &self.body
+    }
+}
+
+/// A parameter of a function. The default is evaluated on each call that
+/// leaves the parameter out, and a variadic parameter, always the last one,
+/// collects the remaining positional arguments into a list.
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pattern: Pattern,
+    default: Option<Expr>,
+    variadic: bool,
+}
+
+impl Match {
+    pub fn new(keyword: Token, value: Expr, arms: Vec<MatchArm>) -> Self {
+        Self {
+            keyword,
+            value,
+            arms,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn value(&self) -> &Expr {
+        &self.value
+    }
+
+    pub fn arms(&self) -> &Vec<MatchArm> {
+        &self.arms
+    }
+}
+
+/// One arm of a `match`, taken when any of its patterns matches and the
+/// guard, if there is one, holds.
+#[derive(Clone, Debug)]
+pub struct MatchArm {
+    patterns: Vec<Pattern>,
+    guard: Option<Expr>,
+    body: Expr,
+}
+
+impl MatchArm {
+    pub fn new(patterns: Vec<Pattern>, guard: Option<Expr>, body: Expr) -> Self {
+        Self {
+            patterns,
+            guard,
+            body,
+        }
+    }
+
+    pub fn patterns(&self) -> &Vec<Pattern> {
+        &self.patterns
+    }
+
+    pub fn guard(&self) -> &Option<Expr> {
+        &self.guard
+    }
+
+    pub fn body(&self) -> &Expr {
+        &self.body
+    }
+
+    /// Whether the arm matches every value, so later arms are never needed.
+    pub fn is_catch_all(&self) -> bool {
+        self.guard.is_none()
+            && self
+                .patterns
+                .iter()
+                .any(|pattern| matches!(pattern, Pattern::Name(_) | Pattern::Wildcard(_)))
+    }
+}
+
+impl Parameter {
+    pub fn new(pattern: Pattern, default: Option<Expr>, variadic: bool) -> Self {
+        Self {
+            pattern,
+            default,
+            variadic,
+        }
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// The name to pass the argument by, which destructured parameters lack.
+    pub fn name(&self) -> Option<&Token> {
+        match &self.pattern {
+            Pattern::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn default(&self) -> &Option<Expr> {
+        &self.default
+    }
+
+    pub fn variadic(&self) -> bool {
+        self.variadic
+    }
+}
+
+/// What a declaration binds: a single name, or a list or map unpacked
+/// into several names. Match arms may also use literals and `_`.
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    Name(Token),
+    // A literal value, which may be negated like `-1`.
+    Literal {
+        token: Token,
+        value: Expr,
+    },
+    Wildcard(Token),
+    // `Color.Custom(r, g, b)`. Without the parentheses any values match.
+    Variant {
+        enum_name: Token,
+        variant: Token,
+        fields: Option<Vec<Pattern>>,
+    },
+    // `[a, b, ...rest]`. The bracket is kept to report shape mismatches.
+    List {
+        bracket: Token,
+        elements: Vec<Pattern>,
+        rest: Option<Token>,
+    },
+    // `(a, b)`, which only matches tuples of the same length.
+    Tuple {
+        paren: Token,
+        elements: Vec<Pattern>,
+    },
+    // `{name, age: years}`, each key with the pattern for its value.
+    Map {
+        brace: Token,
+        entries: Vec<(Token, Pattern)>,
+    },
+    // `Point{x, y: 0}`, matching instances of the class and their fields.
+    Instance {
+        class: Token,
+        fields: Vec<(Token, Pattern)>,
+    },
+}
+
+impl Pattern {
+    /// The token that starts the pattern, where errors about it are reported.
+    pub fn token(&self) -> &Token {
+        match self {
+            Pattern::Name(name) => name,
+            Pattern::Literal { token, .. } => token,
+            Pattern::Wildcard(underscore) => underscore,
+            Pattern::Variant { enum_name, .. } => enum_name,
+            Pattern::List { bracket, .. } => bracket,
+            Pattern::Tuple { paren, .. } => paren,
+            Pattern::Map { brace, .. } => brace,
+            Pattern::Instance { class, .. } => class,
+        }
+    }
+
+    /// Every name the pattern binds, in the order they appear.
+    pub fn names(&self) -> Vec<&Token> {
+        match self {
+            Pattern::Name(name) => vec![name],
+            Pattern::Literal { .. } | Pattern::Wildcard(_) => vec![],
+            Pattern::Variant { fields, .. } => fields
+                .iter()
+                .flatten()
+                .flat_map(|field| field.names())
+                .collect(),
+            Pattern::List { elements, rest, .. } => elements
+                .iter()
+                .flat_map(|element| element.names())
+                .chain(rest)
+                .collect(),
+            Pattern::Tuple { elements, .. } => elements
+                .iter()
+                .flat_map(|element| element.names())
+                .collect(),
+            Pattern::Map { entries, .. }
+            | Pattern::Instance {
+                fields: entries, ..
+            } => entries
+                .iter()
+                .flat_map(|(_, pattern)| pattern.names())
+                .collect(),
+        }
+    }
+}
+
+impl List {
+    pub fn new(elements: Vec<Expr>) -> Self {
+        Self { elements }
+    }
+
+    pub fn elements(&self) -> &Vec<Expr> {
+        &self.elements
+    }
+}
+
 impl Literal {
     pub fn new(literal: LiteralValue) -> Self {
         Self { value: literal }
@@ -151,6 +792,147 @@ impl Literal {
     }
 }
 
+impl Logical {
+    pub fn new(left: Expr, operator: Token, right: Expr) -> Self {
+        Self {
+            left,
+            operator,
+            right,
+        }
+    }
+
+    pub fn left(&self) -> &Expr {
+        &self.left
+    }
+
+    pub fn operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn right(&self) -> &Expr {
+        &self.right
+    }
+}
+
+impl Map {
+    pub fn new(brace: Token, entries: Vec<(Expr, Expr)>) -> Self {
+        Self { brace, entries }
+    }
+
+    pub fn brace(&self) -> &Token {
+        &self.brace
+    }
+
+    pub fn entries(&self) -> &Vec<(Expr, Expr)> {
+        &self.entries
+    }
+}
+
+impl Range {
+    pub fn new(start: Expr, operator: Token, end: Expr) -> Self {
+        Self {
+            start,
+            operator,
+            end,
+        }
+    }
+
+    pub fn start(&self) -> &Expr {
+        &self.start
+    }
+
+    pub fn operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn end(&self) -> &Expr {
+        &self.end
+    }
+}
+
+impl Set {
+    pub fn new(object: Expr, name: Token, value: Expr) -> Self {
+        Self {
+            object,
+            name,
+            value,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Expr {
+        &self.value
+    }
+}
+
+impl SetIndex {
+    pub fn new(object: Expr, bracket: Token, index: Expr, value: Expr) -> Self {
+        Self {
+            object,
+            bracket,
+            index,
+            value,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn index(&self) -> &Expr {
+        &self.index
+    }
+
+    pub fn value(&self) -> &Expr {
+        &self.value
+    }
+}
+
+impl Super {
+    pub fn new(keyword: Token, method: Token) -> Self {
+        Self { keyword, method }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn method(&self) -> &Token {
+        &self.method
+    }
+}
+
+impl This {
+    pub fn new(keyword: Token) -> Self {
+        Self { keyword }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+}
+
+impl Tuple {
+    pub fn new(elements: Vec<Expr>) -> Self {
+        Self { elements }
+    }
+
+    pub fn elements(&self) -> &Vec<Expr> {
+        &self.elements
+    }
+}
+
 impl Unary {
     pub fn new(operator: Token, right: Expr) -> Self {
         Self { operator, right }
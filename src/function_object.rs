@@ -0,0 +1,143 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crate::{environment::Environment, expr::Parameter, literal_object::Literal, stmt::Stmt};
+
+/// A function value, made from a `fun` declaration or a lambda, together
+/// with the scope it was created in.
+pub struct Function {
+    name: Option<String>,
+    params: Vec<Parameter>,
+    body: Vec<Stmt>,
+    closure: Rc<RefCell<Environment>>,
+    // Whether the body yields, so that calls return a generator.
+    generator: bool,
+    // Whether this is a class's `init`, whose calls give back the instance.
+    initializer: bool,
+}
+
+/// A function built into the interpreter, like `implements`. It reports
+/// errors as a message, which the caller attaches to the call.
+pub struct NativeFunction {
+    name: &'static str,
+    arity: usize,
+    function: fn(&[Literal]) -> Result<Literal, String>,
+}
+
+impl Function {
+    pub fn new(
+        name: Option<String>,
+        params: Vec<Parameter>,
+        body: Vec<Stmt>,
+        closure: Rc<RefCell<Environment>>,
+        initializer: bool,
+    ) -> Self {
+        let generator = contains_yield(&body);
+        Function {
+            name,
+            params,
+            body,
+            closure,
+            generator,
+            initializer,
+        }
+    }
+
+    /// The same function with `this` standing for `instance`, the way a
+    /// method runs when it is read from an instance.
+    pub fn bind(&self, instance: Literal) -> Function {
+        let mut environment = Environment::new_enclosed(self.closure.clone());
+        environment.define("this", instance);
+        Function {
+            name: self.name.clone(),
+            params: self.params.clone(),
+            body: self.body.clone(),
+            closure: Rc::new(RefCell::new(environment)),
+            generator: self.generator,
+            initializer: self.initializer,
+        }
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn params(&self) -> &Vec<Parameter> {
+        &self.params
+    }
+
+    pub fn body(&self) -> &Vec<Stmt> {
+        &self.body
+    }
+
+    pub fn closure(&self) -> &Rc<RefCell<Environment>> {
+        &self.closure
+    }
+
+    pub fn is_generator(&self) -> bool {
+        self.generator
+    }
+
+    pub fn is_initializer(&self) -> bool {
+        self.initializer
+    }
+}
+
+impl NativeFunction {
+    pub fn new(
+        name: &'static str,
+        arity: usize,
+        function: fn(&[Literal]) -> Result<Literal, String>,
+    ) -> Self {
+        NativeFunction {
+            name,
+            arity,
+            function,
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn call(&self, arguments: &[Literal]) -> Result<Literal, String> {
+        (self.function)(arguments)
+    }
+}
+
+// Functions declared inside `body` are left out, their yields are their own.
+fn contains_yield(body: &[Stmt]) -> bool {
+    body.iter().any(|stmt| match stmt {
+        Stmt::Yield(_) => true,
+        Stmt::Block(block) => contains_yield(block.statements()),
+        Stmt::ForIn(for_in) => contains_yield(std::slice::from_ref(for_in.body())),
+        _ => false,
+    })
+}
+
+// The closure can hold the function itself, so it is left out.
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "<fn {}>", name),
+            None => write!(f, "<fn>"),
+        }
+    }
+}
+
+impl fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
@@ -0,0 +1,78 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crate::{
+    environment::Environment,
+    literal_object::Literal,
+    stmt::{ForIn, Stmt},
+};
+
+/// What a `for-in` loop walks through. Generators and the iterators of user
+/// classes can only be advanced by the interpreter, so they are kept apart
+/// from plain iterators.
+pub enum Items {
+    Values(Box<dyn Iterator<Item = Literal>>),
+    Generator(Rc<Generator>),
+    // An instance returned by `iter()`, whose `next()` gives nil at the end.
+    Iterator(Literal),
+}
+
+/// One level of a suspended generator body.
+pub enum Frame {
+    // The statements of a block, with the position of the next one to run.
+    Block {
+        statements: Vec<Stmt>,
+        next: usize,
+        environment: Rc<RefCell<Environment>>,
+    },
+    // A `for-in` loop between two passes through its body.
+    Loop {
+        stmt: Rc<ForIn>,
+        items: Items,
+        environment: Rc<RefCell<Environment>>,
+    },
+}
+
+/// A call to a generator function, suspended where it last yielded. Blocks
+/// and loops it is inside of are kept as frames, innermost last, so that
+/// they can be picked up again. It has finished once no frames are left.
+pub struct Generator {
+    name: Option<String>,
+    frames: RefCell<Vec<Frame>>,
+}
+
+impl Generator {
+    pub fn new(
+        name: Option<String>,
+        body: Vec<Stmt>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Self {
+        Generator {
+            name,
+            frames: RefCell::new(vec![Frame::Block {
+                statements: body,
+                next: 0,
+                environment,
+            }]),
+        }
+    }
+
+    pub fn frames(&self) -> &RefCell<Vec<Frame>> {
+        &self.frames
+    }
+}
+
+// The frames hold environments that can hold the generator itself.
+impl fmt::Debug for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "<generator {}>", name),
+            None => write!(f, "<generator>"),
+        }
+    }
+}
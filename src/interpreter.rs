@@ -1,13 +1,32 @@
 use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::rc::Rc;
 
-use crate::environment::Environment;
-use crate::expr::{Accept as AcceptExpr, Expr, Visitor};
+use crate::big_int::BigInt;
+use crate::class_object::{Class, Instance, Members, Trait};
+use crate::decimal::{Decimal, RoundingMode};
+use crate::enum_object::{Enum, EnumValue};
+use crate::environment::{Binding, Environment};
+use crate::expr::{Accept as AcceptExpr, Expr, MatchArm, Pattern, Visitor};
+use crate::function_object::{Function, NativeFunction};
+use crate::generator_object::{Frame, Generator, Items};
 use crate::literal_object::Literal;
+use crate::map_object::Map;
 use crate::stmt::{Accept as AcceptStmt, Stmt, Visitor as VisitorStmt};
 use crate::token::Token;
 use crate::token_type::TokenType;
 
+/// The most elements a range can have when it is turned into a list.
+const MAX_RANGE_LENGTH: i128 = 10_000_000;
+
+/// The most bits the result of `**` or `<<` may take, roughly. Anything
+/// larger takes too long to compute to be what a script meant.
+const MAX_RESULT_BITS: f64 = (1 << 18) as f64;
+
+/// The most fractional digits a decimal can be rounded to.
+const MAX_DECIMAL_SCALE: i64 = 10_000;
+
 #[derive(Debug)]
 pub struct RuntimeError {
     pub token: Token,
@@ -23,47 +42,275 @@ impl RuntimeError {
     }
 }
 
+/// Why a statement stopped executing before reaching its end: a runtime
+/// error, a `break`/`continue` travelling out to the loop it targets, or a
+/// `return` carrying its value out to the function call.
+#[derive(Debug)]
+pub enum Unwind {
+    Error(RuntimeError),
+    Break(Option<Token>),
+    Continue(Option<Token>),
+    Return(Literal),
+}
+
+impl From<RuntimeError> for Unwind {
+    fn from(error: RuntimeError) -> Self {
+        Unwind::Error(error)
+    }
+}
+
+// Two numbers brought to a common type for arithmetic or comparison.
+enum Operands {
+    Integers(i64, i64),
+    BigInts(BigInt, BigInt),
+    Decimals(Decimal, Decimal),
+    Floats(f64, f64),
+}
+
 pub struct Interpreter {
-    pub environment: Environment,
+    pub environment: Rc<RefCell<Environment>>,
+    // The lists and maps being printed further up, so that one inside itself
+    // is shown as `[...]` or `{...}`.
+    printing: Vec<*const ()>,
 }
 
-impl VisitorStmt<Result<(), RuntimeError>> for Interpreter {
-    fn visit_expression_stmt(
-        &mut self,
-        stmt: &crate::stmt::Expression,
-    ) -> Result<(), RuntimeError> {
-        let result = self.evaluate(stmt.expression());
-        match result {
-            Ok(_) => Ok(()),
-            Err(error) => Err(error),
+impl VisitorStmt<Result<(), Unwind>> for Interpreter {
+    fn visit_expression_stmt(&mut self, stmt: &crate::stmt::Expression) -> Result<(), Unwind> {
+        self.evaluate(stmt.expression())?;
+        Ok(())
+    }
+
+    fn visit_print_stmt(&mut self, stmt: &crate::stmt::Print) -> Result<(), Unwind> {
+        let value = self.evaluate(stmt.expression())?;
+        println!("{}", self.stringify(value, stmt.keyword())?);
+        Ok(())
+    }
+
+    fn visit_var_stmt(&mut self, stmt: &crate::stmt::Var) -> Result<(), Unwind> {
+        let value = self.evaluate(stmt.initializer())?;
+        let binding = match stmt.keyword().type_ {
+            TokenType::LET => Binding::Let,
+            TokenType::CONST => Binding::Const,
+            _ => Binding::Var,
+        };
+        for (name, value) in self.destructure(stmt.pattern(), value)? {
+            self.environment
+                .borrow_mut()
+                .declare(&name, value, binding)?;
         }
+        Ok(())
     }
 
-    fn visit_print_stmt(&mut self, stmt: &crate::stmt::Print) -> Result<(), RuntimeError> {
-        let value = self.evaluate(stmt.expression());
-        match value {
-            Ok(value) => {
-                println!("{}", self.stringify(value));
-                Ok(())
+    fn visit_block_stmt(&mut self, stmt: &crate::stmt::Block) -> Result<(), Unwind> {
+        let new_env = Environment::new_enclosed(self.environment.clone());
+        self.execute_block(stmt.statements().to_vec(), Rc::new(RefCell::new(new_env)))
+    }
+
+    fn visit_break_stmt(&mut self, stmt: &crate::stmt::Break) -> Result<(), Unwind> {
+        Err(Unwind::Break(stmt.label().clone()))
+    }
+
+    fn visit_continue_stmt(&mut self, stmt: &crate::stmt::Continue) -> Result<(), Unwind> {
+        Err(Unwind::Continue(stmt.label().clone()))
+    }
+
+    fn visit_function_stmt(&mut self, stmt: &crate::stmt::Function) -> Result<(), Unwind> {
+        let function = Function::new(
+            Some(stmt.name().lexeme.clone()),
+            stmt.lambda().params().clone(),
+            stmt.lambda().body().clone(),
+            self.environment.clone(),
+            false,
+        );
+        self.environment.borrow_mut().declare(
+            stmt.name(),
+            Literal::Function(Rc::new(function)),
+            Binding::Var,
+        )?;
+        Ok(())
+    }
+
+    fn visit_class_stmt(&mut self, stmt: &crate::stmt::Class) -> Result<(), Unwind> {
+        let superclass = match stmt.superclass() {
+            Some(name) => match self.environment.borrow().get(name.clone())? {
+                Literal::Class(class) => Some(class),
+                _ => {
+                    return Err(
+                        RuntimeError::new(name.clone(), "Superclass must be a class").into(),
+                    )
+                }
+            },
+            None => None,
+        };
+        let mut traits = Vec::new();
+        for name in stmt.traits() {
+            match self.environment.borrow().get(name.clone())? {
+                Literal::Trait(trait_) => traits.push(trait_),
+                _ => return Err(RuntimeError::new(name.clone(), "Can only mix in traits").into()),
             }
-            Err(error) => Err(error),
         }
-    }
 
-    fn visit_var_stmt(&mut self, stmt: &crate::stmt::Var) -> Result<(), RuntimeError> {
-        let value = self.evaluate(stmt.initializer());
-        match value {
-            Ok(value) => {
-                self.environment.define(&stmt.name().lexeme, value);
-                Ok(())
+        // Methods of a subclass find `super` in a scope of their own.
+        let mut closure = self.environment.clone();
+        if let Some(superclass) = &superclass {
+            let mut environment = Environment::new_enclosed(closure);
+            environment.define("super", Literal::Class(superclass.clone()));
+            closure = Rc::new(RefCell::new(environment));
+        }
+        let functions = |functions: &[Rc<crate::stmt::Function>], methods: bool| {
+            functions
+                .iter()
+                .map(|function| {
+                    let name = function.name().lexeme.clone();
+                    let initializer = methods && name == "init";
+                    let function = Function::new(
+                        Some(name.clone()),
+                        function.lambda().params().clone(),
+                        function.lambda().body().clone(),
+                        closure.clone(),
+                        initializer,
+                    );
+                    (name, Rc::new(function))
+                })
+                .collect()
+        };
+        let mut methods: HashMap<String, Rc<Function>> = functions(stmt.methods(), true);
+
+        // Trait methods fill in whatever the class doesn't define itself. Two
+        // traits offering the same one is an error the class has to settle
+        // by defining it.
+        let mut mixed_in: HashMap<&str, &Rc<Trait>> = HashMap::new();
+        for (trait_, name) in traits.iter().zip(stmt.traits()) {
+            for (method, function) in trait_.methods() {
+                if methods.contains_key(method) && !mixed_in.contains_key(method.as_str()) {
+                    continue;
+                }
+                if let Some(other) = mixed_in.get(method.as_str()) {
+                    let message = format!(
+                        "Method '{}' is defined by both traits '{}' and '{}'",
+                        method,
+                        other.name(),
+                        trait_.name()
+                    );
+                    return Err(RuntimeError::new(name.clone(), &message).into());
+                }
+                mixed_in.insert(method, trait_);
+                methods.insert(method.clone(), function.clone());
+            }
+        }
+
+        let members = Members {
+            methods,
+            getters: functions(stmt.getters(), false),
+            setters: functions(stmt.setters(), false),
+            statics: functions(stmt.statics(), false),
+        };
+
+        let class = Class::new(stmt.name().lexeme.clone(), superclass, traits, members);
+        for (trait_, name) in class.traits().iter().zip(stmt.traits()) {
+            for method in trait_.required() {
+                if class.method(method).is_none() {
+                    let message = format!(
+                        "Class '{}' must define '{}' required by trait '{}'",
+                        class.name(),
+                        method,
+                        trait_.name()
+                    );
+                    return Err(RuntimeError::new(name.clone(), &message).into());
+                }
             }
-            Err(error) => Err(error),
         }
+        self.environment.borrow_mut().declare(
+            stmt.name(),
+            Literal::Class(Rc::new(class)),
+            Binding::Var,
+        )?;
+        Ok(())
     }
 
-    fn visit_block_stmt(&mut self, stmt: &crate::stmt::Block) -> Result<(), RuntimeError> {
-        let new_env = Environment::new_enclosed(Rc::new(RefCell::new(self.environment.clone())));
-        self.execute_block(stmt.statements().to_vec(), new_env)
+    fn visit_trait_stmt(&mut self, stmt: &crate::stmt::Trait) -> Result<(), Unwind> {
+        let methods = stmt
+            .methods()
+            .iter()
+            .map(|function| {
+                let name = function.name().lexeme.clone();
+                let function = Function::new(
+                    Some(name.clone()),
+                    function.lambda().params().clone(),
+                    function.lambda().body().clone(),
+                    self.environment.clone(),
+                    name == "init",
+                );
+                (name, Rc::new(function))
+            })
+            .collect();
+        let required = stmt
+            .required()
+            .iter()
+            .map(|name| name.lexeme.clone())
+            .collect();
+        let trait_ = Trait::new(stmt.name().lexeme.clone(), methods, required);
+        self.environment.borrow_mut().declare(
+            stmt.name(),
+            Literal::Trait(Rc::new(trait_)),
+            Binding::Var,
+        )?;
+        Ok(())
+    }
+
+    fn visit_enum_stmt(&mut self, stmt: &crate::stmt::Enum) -> Result<(), Unwind> {
+        let variants = stmt
+            .variants()
+            .iter()
+            .map(|variant| {
+                let fields = variant
+                    .fields()
+                    .iter()
+                    .map(|field| field.lexeme.clone())
+                    .collect();
+                (variant.name().lexeme.clone(), fields)
+            })
+            .collect();
+        let enum_ = Enum::new(stmt.name().lexeme.clone(), variants);
+        self.environment.borrow_mut().declare(
+            stmt.name(),
+            Literal::Enum(Rc::new(enum_)),
+            Binding::Var,
+        )?;
+        Ok(())
+    }
+
+    // Generators run their yields through `resume`, so one reached here is
+    // outside a generator.
+    fn visit_yield_stmt(&mut self, stmt: &crate::stmt::Yield) -> Result<(), Unwind> {
+        Err(RuntimeError::new(stmt.keyword().clone(), "Can only yield inside a generator").into())
+    }
+
+    fn visit_return_stmt(&mut self, stmt: &crate::stmt::Return) -> Result<(), Unwind> {
+        let value = match stmt.value() {
+            Some(value) => self.evaluate(value)?,
+            None => Literal::Null,
+        };
+        Err(Unwind::Return(value))
+    }
+
+    fn visit_for_in_stmt(&mut self, stmt: &crate::stmt::ForIn) -> Result<(), Unwind> {
+        let mut items = self.for_in_items(stmt)?;
+        while let Some(item) = self.next_item(&mut items, stmt.pattern().token())? {
+            let mut environment = Environment::new_enclosed(self.environment.clone());
+            for (name, value) in self.destructure(stmt.pattern(), item)? {
+                environment.define(&name.lexeme, value);
+            }
+
+            let environment = Rc::new(RefCell::new(environment));
+            match self.execute_block(vec![stmt.body().clone()], environment) {
+                Ok(()) => {}
+                Err(Unwind::Break(label)) if self.targets_loop(&label, stmt) => break,
+                Err(Unwind::Continue(label)) if self.targets_loop(&label, stmt) => continue,
+                Err(unwind) => return Err(unwind),
+            }
+        }
+        Ok(())
     }
 }
 
@@ -71,65 +318,188 @@ impl Visitor<Result<Literal, RuntimeError>> for Interpreter {
     fn visit_binary_expr(&mut self, expr: &crate::expr::Binary) -> Result<Literal, RuntimeError> {
         let left = self.evaluate(expr.left())?;
         let right = self.evaluate(expr.right())?;
-        match expr.operator().type_ {
-            TokenType::GREATER => self.eval_comparison_binary_op(
-                left,
-                right,
-                |l, r| l > r,
-                ">",
-                expr.operator().clone(),
-            ),
-            TokenType::GREATER_EQUAL => self.eval_comparison_binary_op(
-                left,
-                right,
-                |l, r| l >= r,
-                ">=",
-                expr.operator().clone(),
-            ),
-            TokenType::LESS => self.eval_comparison_binary_op(
-                left,
-                right,
-                |l, r| l < r,
-                "<",
-                expr.operator().clone(),
-            ),
-            TokenType::LESS_EQUAL => self.eval_comparison_binary_op(
-                left,
-                right,
-                |l, r| l <= r,
-                "<=",
+        self.binary_op(left, right, expr.operator())
+    }
+
+    fn visit_call_expr(&mut self, expr: &crate::expr::Call) -> Result<Literal, RuntimeError> {
+        Ok(self.call_link(expr)?.unwrap_or(Literal::Null))
+    }
+
+    fn visit_get_expr(&mut self, expr: &crate::expr::Get) -> Result<Literal, RuntimeError> {
+        Ok(self.get_link(expr)?.unwrap_or(Literal::Null))
+    }
+
+    fn visit_index_expr(&mut self, expr: &crate::expr::Index) -> Result<Literal, RuntimeError> {
+        Ok(self.index_link(expr)?.unwrap_or(Literal::Null))
+    }
+
+    fn visit_list_expr(&mut self, expr: &crate::expr::List) -> Result<Literal, RuntimeError> {
+        let mut elements = Vec::new();
+        for element in expr.elements() {
+            elements.push(self.evaluate(element)?);
+        }
+        Ok(Literal::List(Rc::new(RefCell::new(elements))))
+    }
+
+    fn visit_tuple_expr(&mut self, expr: &crate::expr::Tuple) -> Result<Literal, RuntimeError> {
+        let mut elements = Vec::new();
+        for element in expr.elements() {
+            elements.push(self.evaluate(element)?);
+        }
+        Ok(Literal::Tuple(Rc::new(elements)))
+    }
+
+    fn visit_map_expr(&mut self, expr: &crate::expr::Map) -> Result<Literal, RuntimeError> {
+        let mut map = Map::default();
+        for (key, value) in expr.entries() {
+            let key = self.evaluate(key)?;
+            let key = self.map_key(key, expr.brace())?;
+            let value = self.evaluate(value)?;
+            map.insert(key, value);
+        }
+        Ok(Literal::Map(Rc::new(RefCell::new(map))))
+    }
+
+    fn visit_range_expr(&mut self, expr: &crate::expr::Range) -> Result<Literal, RuntimeError> {
+        let (start, end) = self.range_bounds(expr)?;
+        // `for` loops count ranges lazily, but a range used as a value is
+        // built as a list, so it has to fit in memory.
+        if (end as i128 - start as i128) > MAX_RANGE_LENGTH {
+            return Err(RuntimeError::new(
                 expr.operator().clone(),
-            ),
-            TokenType::MINUS => {
-                self.eval_number_binary_op(left, right, |l, r| l - r, "-", expr.operator().clone())
-            }
-            TokenType::SLASH => {
-                self.eval_number_binary_op(left, right, |l, r| l / r, "/", expr.operator().clone())
-            }
-            TokenType::STAR => {
-                self.eval_number_binary_op(left, right, |l, r| l * r, "*", expr.operator().clone())
-            }
-            TokenType::PLUS => match (right, left) {
-                (Literal::Number(l), Literal::Number(r)) => self.eval_number_binary_op(
-                    Literal::Number(l),
-                    Literal::Number(r),
-                    |l, r| l + r,
-                    "+",
-                    expr.operator().clone(),
-                ),
-                (Literal::String(l), Literal::String(r)) => {
-                    Ok(Literal::String(format!("{}{}", l, r)))
+                &format!("Range is longer than {} elements", MAX_RANGE_LENGTH),
+            ));
+        }
+        let numbers = (start..end).map(Literal::Integer).collect();
+        Ok(Literal::List(Rc::new(RefCell::new(numbers))))
+    }
+
+    fn visit_set_index_expr(
+        &mut self,
+        expr: &crate::expr::SetIndex,
+    ) -> Result<Literal, RuntimeError> {
+        let object = self.evaluate(expr.object())?;
+        let index = self.evaluate(expr.index())?;
+        let value = self.evaluate(expr.value())?;
+        self.set_index(object, index, value.clone(), expr.bracket())?;
+        Ok(value)
+    }
+
+    fn visit_compound_expr(
+        &mut self,
+        expr: &crate::expr::Compound,
+    ) -> Result<Literal, RuntimeError> {
+        let operator = self.update_operator(expr.operator());
+        let (_, new) = self.update_target(expr.target(), expr.operator(), &mut |this, old| {
+            let value = this.evaluate(expr.value())?;
+            this.binary_op(old, value, &operator)
+        })?;
+        Ok(new)
+    }
+
+    fn visit_increment_expr(
+        &mut self,
+        expr: &crate::expr::Increment,
+    ) -> Result<Literal, RuntimeError> {
+        let operator = self.update_operator(expr.operator());
+        let (old, new) = self.update_target(expr.target(), expr.operator(), &mut |this, old| {
+            this.binary_op(old, Literal::Integer(1), &operator)
+        })?;
+        match expr.prefix() {
+            true => Ok(new),
+            false => Ok(old),
+        }
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &crate::expr::Lambda) -> Result<Literal, RuntimeError> {
+        let function = Function::new(
+            None,
+            expr.params().clone(),
+            expr.body().clone(),
+            self.environment.clone(),
+            false,
+        );
+        Ok(Literal::Function(Rc::new(function)))
+    }
+
+    fn visit_set_expr(&mut self, expr: &crate::expr::Set) -> Result<Literal, RuntimeError> {
+        let object = self.evaluate(expr.object())?;
+        let value = self.evaluate(expr.value())?;
+        self.set_property(object, expr.name(), value.clone())?;
+        Ok(value)
+    }
+
+    fn visit_this_expr(&mut self, expr: &crate::expr::This) -> Result<Literal, RuntimeError> {
+        self.environment.borrow().get(expr.keyword().clone())
+    }
+
+    // Looks the method up from the superclass, but runs it on `this`.
+    fn visit_super_expr(&mut self, expr: &crate::expr::Super) -> Result<Literal, RuntimeError> {
+        let Literal::Class(superclass) = self.environment.borrow().get(expr.keyword().clone())?
+        else {
+            unreachable!("`super` is only defined as a class")
+        };
+        let mut this = expr.keyword().clone();
+        this.lexeme = String::from("this");
+        let instance = self.environment.borrow().get(this)?;
+
+        match self.class_property(&superclass, instance, expr.method())? {
+            Some(value) => Ok(value),
+            None => Err(RuntimeError::new(
+                expr.method().clone(),
+                &format!("Undefined property '{}'", expr.method().lexeme),
+            )),
+        }
+    }
+
+    fn visit_match_expr(&mut self, expr: &crate::expr::Match) -> Result<Literal, RuntimeError> {
+        let value = self.evaluate(expr.value())?;
+        for arm in expr.arms() {
+            for pattern in arm.patterns() {
+                let mut bindings = Vec::new();
+                if !self.matches(pattern, &value, &mut bindings)? {
+                    continue;
                 }
-                _ => Err(RuntimeError::new(
-                    expr.operator().clone(),
-                    "Operands must be two numbers or two strings",
-                )),
-            },
-            TokenType::BANG_EQUAL => Ok(Literal::Boolean(!self.is_equals(left, right))),
-            TokenType::EQUAL_EQUAL => Ok(Literal::Boolean(self.is_equals(left, right))),
+
+                let mut environment = Environment::new_enclosed(self.environment.clone());
+                for (name, value) in bindings {
+                    environment.define(&name.lexeme, value);
+                }
+                let environment = Rc::new(RefCell::new(environment));
+                let previous = std::mem::replace(&mut self.environment, environment);
+                let result = self.run_match_arm(arm);
+                self.environment = previous;
+                if let Some(result) = result? {
+                    return Ok(result);
+                }
+            }
+        }
+        let value = self.stringify(value, expr.keyword())?;
+        Err(RuntimeError::new(
+            expr.keyword().clone(),
+            &format!("No match arm for {}", value),
+        ))
+    }
+
+    fn visit_conditional_expr(
+        &mut self,
+        expr: &crate::expr::Conditional,
+    ) -> Result<Literal, RuntimeError> {
+        let condition = self.evaluate(expr.condition())?;
+        match self.is_truthy(condition) {
+            true => self.evaluate(expr.then_branch()),
+            false => self.evaluate(expr.else_branch()),
+        }
+    }
+
+    fn visit_logical_expr(&mut self, expr: &crate::expr::Logical) -> Result<Literal, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        match (expr.operator().type_, left) {
+            (TokenType::QUESTION_QUESTION, Literal::Null) => self.evaluate(expr.right()),
+            (TokenType::QUESTION_QUESTION, left) => Ok(left),
             _ => Err(RuntimeError::new(
                 expr.operator().clone(),
-                "Binary expression error",
+                "Logical expression error",
             )),
         }
     }
@@ -141,28 +511,48 @@ impl Visitor<Result<Literal, RuntimeError>> for Interpreter {
         Ok(self.evaluate(&expr.expression())?)
     }
 
+    fn visit_interpolation_expr(
+        &mut self,
+        expr: &crate::expr::Interpolation,
+    ) -> Result<Literal, RuntimeError> {
+        let mut text = String::new();
+        for part in expr.parts() {
+            let value = self.evaluate(part)?;
+            text.push_str(&self.stringify(value, expr.start())?);
+        }
+        Ok(Literal::String(text))
+    }
+
     fn visit_literal_expr(&self, expr: &crate::expr::Literal) -> Result<Literal, RuntimeError> {
         Ok(expr.value().clone())
     }
 
     fn visit_unary_expr(&mut self, expr: &crate::expr::Unary) -> Result<Literal, RuntimeError> {
-        let right = self.evaluate(expr.right());
+        let right = self.evaluate(expr.right())?;
 
         match expr.operator().type_ {
             TokenType::MINUS => match right {
-                Ok(Literal::Number(n)) => return Ok(Literal::Number(-n)),
+                Literal::Number(n) => return Ok(Literal::Number(-n)),
+                Literal::Integer(n) => Ok(match n.checked_neg() {
+                    Some(n) => Literal::Integer(n),
+                    None => Literal::integer(BigInt::from_i64(n).neg()),
+                }),
+                Literal::BigInt(n) => Ok(Literal::integer(n.neg())),
+                Literal::Decimal(n) => Ok(Literal::Decimal(n.neg())),
                 _ => Err(RuntimeError::new(
                     expr.operator().clone(),
                     "Error casting number",
                 )),
             },
-            TokenType::BANG => match right {
-                Ok(right) => return Ok(Literal::Boolean(self.is_truthy(right))),
+            TokenType::TILDE => match right {
+                Literal::Integer(n) => Ok(Literal::Integer(!n)),
+                Literal::BigInt(n) => Ok(Literal::integer(n.not())),
                 _ => Err(RuntimeError::new(
                     expr.operator().clone(),
-                    "Unary must have a valid value",
+                    "Operand of '~' must be an integer",
                 )),
             },
+            TokenType::BANG => return Ok(Literal::Boolean(self.is_truthy(right))),
             _ => Err(RuntimeError::new(
                 expr.operator().clone(),
                 "Unary expression error",
@@ -174,16 +564,16 @@ impl Visitor<Result<Literal, RuntimeError>> for Interpreter {
         &mut self,
         expr: &crate::expr::Variable,
     ) -> Result<Literal, RuntimeError> {
-        self.environment.get(expr.name().clone())
+        self.environment.borrow().get(expr.name().clone())
     }
 
     fn visit_assign_expr(&mut self, expr: &crate::expr::Assign) -> Result<Literal, RuntimeError> {
         let value = self.evaluate(expr.value());
         match value {
             Ok(value) => {
-                if let Err(err) = self.environment.assign(expr.name().clone(), value.clone()) {
-                    return Err(err);
-                }
+                self.environment
+                    .borrow_mut()
+                    .assign(expr.name().clone(), value.clone())?;
                 Ok(value)
             }
             Err(_) => Err(RuntimeError::new(
@@ -194,17 +584,34 @@ impl Visitor<Result<Literal, RuntimeError>> for Interpreter {
     }
 }
 
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Interpreter {
     pub fn new() -> Self {
+        // Built-in functions live a scope above the script's globals, so a
+        // script can declare its own under the same name.
+        let mut builtins = Environment::new(None);
+        let implements = NativeFunction::new("implements", 2, implements);
+        builtins.define("implements", Literal::Native(Rc::new(implements)));
+        let globals = Environment::new_enclosed(Rc::new(RefCell::new(builtins)));
         Interpreter {
-            environment: Environment::new(None),
+            environment: Rc::new(RefCell::new(globals)),
+            printing: Vec::new(),
         }
     }
 
     pub fn interpret(&mut self, statements: Vec<Stmt>) -> Result<(), RuntimeError> {
         for statement in statements {
-            if let Err(error) = self.execute(statement) {
-                return Err(error);
+            match self.execute(statement) {
+                Ok(_) => {}
+                Err(Unwind::Error(error)) => return Err(error),
+                // The parser only accepts `break` and `continue` inside loops,
+                // and `return` inside functions.
+                Err(Unwind::Break(_)) | Err(Unwind::Continue(_)) | Err(Unwind::Return(_)) => {}
             }
         }
         Ok(())
@@ -217,118 +624,1944 @@ impl Interpreter {
         // };
     }
 
-    pub fn execute(&mut self, stmt: Stmt) -> Result<(), RuntimeError> {
+    pub fn execute(&mut self, stmt: Stmt) -> Result<(), Unwind> {
         match stmt {
             Stmt::Expression(expression) => return expression.accept(self),
             Stmt::Print(print) => print.accept(self),
             Stmt::Var(var) => var.accept(self),
             Stmt::Block(block) => block.accept(self),
+            Stmt::Break(break_) => break_.accept(self),
+            Stmt::Continue(continue_) => continue_.accept(self),
+            Stmt::ForIn(for_in) => for_in.accept(self),
+            Stmt::Function(function) => function.accept(self),
+            Stmt::Enum(enum_) => enum_.accept(self),
+            Stmt::Class(class) => class.accept(self),
+            Stmt::Trait(trait_) => trait_.accept(self),
+            Stmt::Yield(yield_) => yield_.accept(self),
+            Stmt::Return(return_) => return_.accept(self),
         }
     }
 
-    fn execute_block(
-        &mut self,
-        statements: Vec<Stmt>,
-        environment: Environment,
-    ) -> Result<(), RuntimeError> {
-        let actual = self.environment.clone();
-        let previous = self.environment.clone();
-        match actual.into() {
-            Some(_) => {
-                self.environment = environment;
+    // Whether a `break` or `continue` with `label` stops at `loop_`, an
+    // unlabeled one always applies to the innermost loop.
+    fn targets_loop(&self, label: &Option<Token>, loop_: &crate::stmt::ForIn) -> bool {
+        match (label, loop_.label()) {
+            (None, _) => true,
+            (Some(label), Some(name)) => label.lexeme == name.lexeme,
+            (Some(_), None) => false,
+        }
+    }
+
+    fn for_in_items(&mut self, stmt: &crate::stmt::ForIn) -> Result<Items, RuntimeError> {
+        match stmt.iterable() {
+            // Ranges are counted lazily instead of being built as a list first.
+            Expr::Range(range) => {
+                let (start, end) = self.range_bounds(range)?;
+                Ok(Items::Values(Box::new((start..end).map(Literal::Integer))))
+            }
+            iterable => {
+                let value = self.evaluate(iterable)?;
+                self.items(value, stmt.pattern().token())
+            }
+        }
+    }
 
-                for statement in statements {
-                    self.execute(statement)?;
+    // The items of `value` for a `for-in` loop. An instance is iterated
+    // through what its `iter()` method returns.
+    fn items(&mut self, value: Literal, token: &Token) -> Result<Items, RuntimeError> {
+        let values: Box<dyn Iterator<Item = Literal>> =
+            match value {
+                Literal::List(list) => {
+                    // Read by position, so elements pushed by the body are visited too.
+                    let mut index = 0;
+                    Box::new(std::iter::from_fn(move || {
+                        let item = list.borrow().get(index).cloned();
+                        index += 1;
+                        item
+                    }))
+                }
+                Literal::Map(map) => {
+                    let keys: Vec<Literal> = map
+                        .borrow()
+                        .entries()
+                        .iter()
+                        .map(|(key, _)| key.clone())
+                        .collect();
+                    Box::new(keys.into_iter())
+                }
+                Literal::String(string) => {
+                    let chars: Vec<Literal> = string
+                        .chars()
+                        .map(|c| Literal::String(c.to_string()))
+                        .collect();
+                    Box::new(chars.into_iter())
                 }
+                Literal::Generator(generator) => return Ok(Items::Generator(generator)),
+                Literal::Instance(_) => {
+                    return match self.call_special_method(&value, "iter", vec![], token)? {
+                        Some(iterator @ Literal::Instance(_)) => Ok(Items::Iterator(iterator)),
+                        Some(iterator) => self.items(iterator, token),
+                        None => Err(RuntimeError::new(
+                            token.clone(),
+                            "Can only iterate over instances with an 'iter' method",
+                        )),
+                    }
+                }
+                _ => return Err(RuntimeError::new(
+                    token.clone(),
+                    "Can only iterate over lists, maps, strings, ranges, generators and instances",
+                )),
+            };
+        Ok(Items::Values(values))
+    }
 
-                self.environment = previous;
-                Ok(())
+    fn next_item(
+        &mut self,
+        items: &mut Items,
+        token: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        match items {
+            Items::Values(values) => Ok(values.next()),
+            Items::Generator(generator) => self.resume(generator, token),
+            Items::Iterator(iterator) => {
+                match self.call_special_method(iterator, "next", vec![], token)? {
+                    Some(Literal::Null) => Ok(None),
+                    Some(item) => Ok(Some(item)),
+                    None => Err(RuntimeError::new(
+                        token.clone(),
+                        "An iterator returned by 'iter' must have a 'next' method",
+                    )),
+                }
             }
-            None => Ok(()),
         }
     }
 
-    fn evaluate(&mut self, expr: &Expr) -> Result<Literal, RuntimeError> {
-        match expr {
-            Expr::Binary(binary) => binary.accept(self),
-            Expr::Grouping(grouping) => grouping.accept(self),
-            Expr::Literal(literal) => literal.accept(self),
-            Expr::Unary(unary) => unary.accept(self),
-            Expr::Variable(variable) => variable.accept(self),
-            Expr::Assign(assign) => assign.accept(self),
+    // Runs `generator` up to its next `yield`, giving None once it has
+    // finished. A generator that fails can't be resumed again.
+    fn resume(
+        &mut self,
+        generator: &Generator,
+        token: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let Ok(mut frames) = generator.frames().try_borrow_mut() else {
+            return Err(RuntimeError::new(
+                token.clone(),
+                "Generator is already running",
+            ));
+        };
+        let result = self.run_frames(&mut frames);
+        if !matches!(result, Ok(Some(_))) {
+            frames.clear();
         }
+        result
     }
 
-    fn is_truthy(&self, literal: Literal) -> bool {
-        match literal {
-            Literal::Null => false,
-            Literal::Boolean(value) => value,
-            _ => true,
+    // Blocks and loops in a generator body are run from `frames` rather than
+    // by `execute`, which could not stop halfway through them. Any other
+    // statement can't yield and runs as usual.
+    fn run_frames(&mut self, frames: &mut Vec<Frame>) -> Result<Option<Literal>, RuntimeError> {
+        while let Some(frame) = frames.last_mut() {
+            match frame {
+                Frame::Block {
+                    statements,
+                    next,
+                    environment,
+                } => {
+                    let Some(stmt) = statements.get(*next).cloned() else {
+                        frames.pop();
+                        continue;
+                    };
+                    *next += 1;
+                    let environment = environment.clone();
+
+                    match stmt {
+                        Stmt::Yield(yield_) => {
+                            let previous = std::mem::replace(&mut self.environment, environment);
+                            let value = match yield_.value() {
+                                Some(value) => self.evaluate(value),
+                                None => Ok(Literal::Null),
+                            };
+                            self.environment = previous;
+                            return Ok(Some(value?));
+                        }
+                        Stmt::Block(block) => frames.push(Frame::Block {
+                            statements: block.statements().clone(),
+                            next: 0,
+                            environment: Rc::new(RefCell::new(Environment::new_enclosed(
+                                environment,
+                            ))),
+                        }),
+                        Stmt::ForIn(for_in) => {
+                            let previous =
+                                std::mem::replace(&mut self.environment, environment.clone());
+                            let items = self.for_in_items(&for_in);
+                            self.environment = previous;
+                            frames.push(Frame::Loop {
+                                stmt: for_in,
+                                items: items?,
+                                environment,
+                            });
+                        }
+                        stmt => match self.execute_block(vec![stmt], environment) {
+                            Ok(()) => {}
+                            Err(Unwind::Error(error)) => return Err(error),
+                            Err(Unwind::Return(_)) => return Ok(None),
+                            Err(Unwind::Break(label)) => self.leave_loop(frames, &label, true),
+                            Err(Unwind::Continue(label)) => self.leave_loop(frames, &label, false),
+                        },
+                    }
+                }
+                Frame::Loop {
+                    stmt,
+                    items,
+                    environment,
+                } => {
+                    let (stmt, environment) = (stmt.clone(), environment.clone());
+                    let Some(item) = self.next_item(items, stmt.pattern().token())? else {
+                        frames.pop();
+                        continue;
+                    };
+                    let mut body_environment = Environment::new_enclosed(environment);
+                    for (name, value) in self.destructure(stmt.pattern(), item)? {
+                        body_environment.define(&name.lexeme, value);
+                    }
+                    frames.push(Frame::Block {
+                        statements: vec![stmt.body().clone()],
+                        next: 0,
+                        environment: Rc::new(RefCell::new(body_environment)),
+                    });
+                }
+            }
         }
+        Ok(None)
     }
 
-    fn eval_number_binary_op<F: Fn(f64, f64) -> f64>(
-        &self,
-        left: Literal,
-        right: Literal,
-        op: F,
-        op_name: &str,
-        token: Token,
-    ) -> Result<Literal, RuntimeError> {
-        match (left, right) {
-            (Literal::Number(left), Literal::Number(right)) => Ok(Literal::Number(op(left, right))),
-            _ => Err(RuntimeError::new(
-                token,
-                &format!("Operator '{}' requires two numbers", op_name),
-            )),
+    // Drops the frames inside the loop a `break` or `continue` with `label`
+    // targets. A `break` drops the loop too, while after a `continue` it goes
+    // on with its next item.
+    fn leave_loop(&self, frames: &mut Vec<Frame>, label: &Option<Token>, leave: bool) {
+        while let Some(frame) = frames.last() {
+            if let Frame::Loop { stmt, .. } = frame {
+                if self.targets_loop(label, stmt) {
+                    if leave {
+                        frames.pop();
+                    }
+                    return;
+                }
+            }
+            frames.pop();
         }
     }
 
-    fn eval_comparison_binary_op<F: Fn(f64, f64) -> bool>(
-        &self,
-        left: Literal,
-        right: Literal,
-        op: F,
-        op_name: &str,
-        token: Token,
-    ) -> Result<Literal, RuntimeError> {
-        match (left, right) {
-            (Literal::Number(left), Literal::Number(right)) => {
-                Ok(Literal::Boolean(op(left, right)))
+    // Evaluates the bounds of `range` as a half-open interval of integers.
+    fn range_bounds(&mut self, range: &crate::expr::Range) -> Result<(i64, i64), RuntimeError> {
+        let start = self.evaluate(range.start())?;
+        let end = self.evaluate(range.end())?;
+        match (start, end) {
+            (Literal::Integer(start), Literal::Integer(end)) => {
+                let inclusive = range.operator().type_ == TokenType::DOT_DOT_EQUAL;
+                Ok((start, end.saturating_add(inclusive as i64)))
             }
             _ => Err(RuntimeError::new(
-                token,
-                &format!("Operator '{}' requires two numbers", op_name),
+                range.operator().clone(),
+                "Range bounds must be integers",
             )),
         }
     }
 
-    fn is_equals(&self, left: Literal, right: Literal) -> bool {
-        if left == Literal::Null && right == Literal::Null {
-            return true;
-        }
+    fn execute_block(
+        &mut self,
+        statements: Vec<Stmt>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<(), Unwind> {
+        let previous = self.environment.clone();
+        self.environment = environment;
 
-        if left == Literal::Null {
-            return false;
+        let mut result = Ok(());
+        for statement in statements {
+            result = self.execute(statement);
+            if result.is_err() {
+                break;
+            }
         }
 
-        left.eq(&right)
+        // Restore the enclosing scope however the block was left.
+        self.environment = previous;
+        result
     }
 
-    fn stringify(&self, value: Literal) -> String {
-        match value {
-            Literal::Null => return String::from("nil"),
-            Literal::Number(value) => {
-                let mut text = value.to_string();
-                if text.ends_with(".0") {
-                    text = text[..text.len() - 2].to_string();
+    // `?.` ends the whole chain of accesses and calls after it, so `a?.b.c()`
+    // is nil when `a` is. The links of a chain are evaluated here instead of
+    // through `evaluate`, and None means a `?.` short-circuited.
+    fn link(&mut self, expr: &Expr) -> Result<Option<Literal>, RuntimeError> {
+        match expr {
+            Expr::Call(call) => self.call_link(call),
+            Expr::Get(get) => self.get_link(get),
+            Expr::Index(index) => self.index_link(index),
+            _ => self.evaluate(expr).map(Some),
+        }
+    }
+
+    fn call_link(&mut self, expr: &crate::expr::Call) -> Result<Option<Literal>, RuntimeError> {
+        match expr.callee() {
+            Expr::Get(get) => {
+                let Some(object) = self.link(get.object())? else {
+                    return Ok(None);
+                };
+                // `a?.method()` skips the arguments too when `a` is nil.
+                if get.optional() && object == Literal::Null {
+                    return Ok(None);
+                }
+                // Methods of instances and classes are read like any other
+                // property, then called.
+                if let Literal::Instance(_) | Literal::Class(_) = object {
+                    let callee = self.get_property(object, get.name())?;
+                    return self.call_value(callee, expr).map(Some);
+                }
+                if let Some((name, _)) = expr.named().first() {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        "Built-in methods don't take named arguments",
+                    ));
+                }
+                let mut arguments = Vec::new();
+                for argument in expr.arguments() {
+                    arguments.push(self.evaluate(argument)?);
                 }
-                return text;
+                self.call_method(object, get.name(), arguments).map(Some)
             }
-            Literal::String(str) => {
-                return str;
+            callee => {
+                let Some(callee) = self.link(callee)? else {
+                    return Ok(None);
+                };
+                self.call_value(callee, expr).map(Some)
+            }
+        }
+    }
+
+    // Evaluates the arguments of `expr` and calls `callee` with them.
+    fn call_value(
+        &mut self,
+        callee: Literal,
+        expr: &crate::expr::Call,
+    ) -> Result<Literal, RuntimeError> {
+        let mut arguments = Vec::new();
+        for argument in expr.arguments() {
+            arguments.push(self.evaluate(argument)?);
+        }
+        let mut named = Vec::new();
+        for (name, argument) in expr.named() {
+            named.push((name.clone(), self.evaluate(argument)?));
+        }
+        match callee {
+            Literal::Function(function) => {
+                self.call_function(&function, arguments, named, expr.paren())
+            }
+            Literal::Class(class) => self.call_class(class, arguments, named, expr.paren()),
+            Literal::Native(native) => {
+                if !named.is_empty() {
+                    return Err(RuntimeError::new(
+                        expr.paren().clone(),
+                        "Built-in functions don't take named arguments",
+                    ));
+                }
+                if arguments.len() != native.arity() {
+                    let message = format!(
+                        "Expected {} arguments but got {}",
+                        native.arity(),
+                        arguments.len()
+                    );
+                    return Err(RuntimeError::new(expr.paren().clone(), &message));
+                }
+                native
+                    .call(&arguments)
+                    .map_err(|message| RuntimeError::new(expr.paren().clone(), &message))
             }
-            Literal::Boolean(bool) => return bool.to_string(),
+            _ => Err(RuntimeError::new(
+                expr.paren().clone(),
+                "Can only call functions and classes",
+            )),
+        }
+    }
+
+    fn get_link(&mut self, expr: &crate::expr::Get) -> Result<Option<Literal>, RuntimeError> {
+        let Some(object) = self.link(expr.object())? else {
+            return Ok(None);
+        };
+        if expr.optional() && object == Literal::Null {
+            return Ok(None);
         }
+        self.get_property(object, expr.name()).map(Some)
     }
+
+    fn get_property(&mut self, object: Literal, name: &Token) -> Result<Literal, RuntimeError> {
+        match object {
+            // Fields come first, then what the class declares.
+            Literal::Instance(instance) => {
+                if let Some(value) = instance.field(&name.lexeme) {
+                    return Ok(value);
+                }
+                let class = instance.class().clone();
+                if let Some(value) =
+                    self.class_property(&class, Literal::Instance(instance), name)?
+                {
+                    return Ok(value);
+                }
+            }
+            Literal::Class(class) => {
+                if let Some(function) = class.static_method(&name.lexeme) {
+                    return Ok(Literal::Function(function.clone()));
+                }
+            }
+            // Only variants without values can be read, the others are
+            // built by calling them.
+            Literal::Enum(enum_) => {
+                let variant = self.enum_variant(&enum_, name)?;
+                let fields = enum_.fields(variant);
+                if !fields.is_empty() {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        &format!("Variant '{}' needs {} values", name.lexeme, fields.len()),
+                    ));
+                }
+                return Ok(Literal::EnumValue(Rc::new(EnumValue::new(
+                    enum_,
+                    variant,
+                    Vec::new(),
+                ))));
+            }
+            Literal::EnumValue(value) => {
+                if let Some(field) = value.field(&name.lexeme) {
+                    return Ok(field.clone());
+                }
+            }
+            // `t.0`, where the parser made the index the property name.
+            Literal::Tuple(tuple) if name.type_ == TokenType::NUMBER => {
+                let index = name.lexeme.parse::<usize>().ok();
+                return match index.and_then(|index| tuple.get(index)) {
+                    Some(element) => Ok(element.clone()),
+                    None => Err(RuntimeError::new(
+                        name.clone(),
+                        &format!(
+                            "Tuple index {} out of range for length {}",
+                            name.lexeme,
+                            tuple.len()
+                        ),
+                    )),
+                };
+            }
+            _ => {}
+        }
+        Err(RuntimeError::new(
+            name.clone(),
+            &format!("Undefined property '{}'", name.lexeme),
+        ))
+    }
+
+    fn index_link(&mut self, expr: &crate::expr::Index) -> Result<Option<Literal>, RuntimeError> {
+        let Some(object) = self.link(expr.object())? else {
+            return Ok(None);
+        };
+        let index = self.evaluate(expr.index())?;
+        self.get_index(object, index, expr.bracket()).map(Some)
+    }
+
+    // Reads what `class` declares under `name` for `instance`: a getter runs
+    // right away, and a method comes back bound to the instance.
+    fn class_property(
+        &mut self,
+        class: &Class,
+        instance: Literal,
+        name: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(getter) = class.getter(&name.lexeme) {
+            let getter = getter.bind(instance);
+            return self.call_function(&getter, vec![], vec![], name).map(Some);
+        }
+        if let Some(method) = class.method(&name.lexeme) {
+            return Ok(Some(Literal::Function(Rc::new(method.bind(instance)))));
+        }
+        Ok(None)
+    }
+
+    // Assigns through a setter if the class has one, otherwise to a field.
+    fn set_property(
+        &mut self,
+        object: Literal,
+        name: &Token,
+        value: Literal,
+    ) -> Result<(), RuntimeError> {
+        let Literal::Instance(instance) = object else {
+            return Err(RuntimeError::new(
+                name.clone(),
+                "Only instances have fields",
+            ));
+        };
+        let class = instance.class().clone();
+        if let Some(setter) = class.setter(&name.lexeme) {
+            let setter = setter.bind(Literal::Instance(instance));
+            self.call_function(&setter, vec![value], vec![], name)?;
+            return Ok(());
+        }
+        if class.getter(&name.lexeme).is_some() {
+            return Err(RuntimeError::new(
+                name.clone(),
+                &format!("Can't set get-only property '{}'", name.lexeme),
+            ));
+        }
+        instance.set_field(&name.lexeme, value);
+        Ok(())
+    }
+
+    // Makes a new instance and runs `init` on it, if the class has one.
+    fn call_class(
+        &mut self,
+        class: Rc<Class>,
+        arguments: Vec<Literal>,
+        named: Vec<(Token, Literal)>,
+        paren: &Token,
+    ) -> Result<Literal, RuntimeError> {
+        let instance = Literal::Instance(Rc::new(Instance::new(class.clone())));
+        match class.method("init") {
+            Some(init) => {
+                self.call_function(&init.bind(instance.clone()), arguments, named, paren)?;
+            }
+            None if !arguments.is_empty() || !named.is_empty() => {
+                return Err(RuntimeError::new(
+                    paren.clone(),
+                    &format!(
+                        "Expected 0 arguments but got {}",
+                        arguments.len() + named.len()
+                    ),
+                ));
+            }
+            None => {}
+        }
+        Ok(instance)
+    }
+
+    fn evaluate(&mut self, expr: &Expr) -> Result<Literal, RuntimeError> {
+        match expr {
+            Expr::Binary(binary) => binary.accept(self),
+            Expr::Grouping(grouping) => grouping.accept(self),
+            Expr::Interpolation(interpolation) => interpolation.accept(self),
+            Expr::Literal(literal) => literal.accept(self),
+            Expr::Unary(unary) => unary.accept(self),
+            Expr::Variable(variable) => variable.accept(self),
+            Expr::Assign(assign) => assign.accept(self),
+            Expr::Call(call) => call.accept(self),
+            Expr::Compound(compound) => compound.accept(self),
+            Expr::Conditional(conditional) => conditional.accept(self),
+            Expr::Logical(logical) => logical.accept(self),
+            Expr::Lambda(lambda) => lambda.accept(self),
+            Expr::Increment(increment) => increment.accept(self),
+            Expr::Get(get) => get.accept(self),
+            Expr::Index(index) => index.accept(self),
+            Expr::List(list) => list.accept(self),
+            Expr::SetIndex(set_index) => set_index.accept(self),
+            Expr::Set(set) => set.accept(self),
+            Expr::This(this) => this.accept(self),
+            Expr::Super(super_) => super_.accept(self),
+            Expr::Map(map) => map.accept(self),
+            Expr::Tuple(tuple) => tuple.accept(self),
+            Expr::Match(match_) => match_.accept(self),
+            Expr::Range(range) => range.accept(self),
+        }
+    }
+
+    // Shared by binary expressions and compound assignments.
+    fn binary_op(
+        &mut self,
+        left: Literal,
+        right: Literal,
+        operator: &Token,
+    ) -> Result<Literal, RuntimeError> {
+        if let Some(result) = self.overloaded_op(&left, &right, operator)? {
+            return Ok(result);
+        }
+        match operator.type_ {
+            TokenType::GREATER => {
+                self.eval_comparison_binary_op(left, right, Ordering::is_gt, ">", operator.clone())
+            }
+            TokenType::GREATER_EQUAL => {
+                self.eval_comparison_binary_op(left, right, Ordering::is_ge, ">=", operator.clone())
+            }
+            TokenType::LESS => {
+                self.eval_comparison_binary_op(left, right, Ordering::is_lt, "<", operator.clone())
+            }
+            TokenType::LESS_EQUAL => {
+                self.eval_comparison_binary_op(left, right, Ordering::is_le, "<=", operator.clone())
+            }
+            // Plain division always yields a float, or a decimal for decimals,
+            // and `~/` is the integer one.
+            TokenType::MINUS
+            | TokenType::SLASH
+            | TokenType::TILDE_SLASH
+            | TokenType::PERCENT
+            | TokenType::STAR => self.eval_number_binary_op(left, right, operator.clone()),
+            TokenType::PLUS => match (left, right) {
+                (Literal::String(l), Literal::String(r)) => {
+                    Ok(Literal::String(format!("{}{}", l, r)))
+                }
+                (l, r) if self.is_number(&l) && self.is_number(&r) => {
+                    self.eval_number_binary_op(l, r, operator.clone())
+                }
+                _ => Err(RuntimeError::new(
+                    operator.clone(),
+                    "Operands must be two numbers or two strings",
+                )),
+            },
+            TokenType::STAR_STAR => self.eval_power_op(left, right, operator.clone()),
+            TokenType::AMPERSAND
+            | TokenType::PIPE
+            | TokenType::CARET
+            | TokenType::LESS_LESS
+            | TokenType::GREATER_GREATER => {
+                self.eval_integer_binary_op(left, right, operator.clone())
+            }
+            TokenType::BANG_EQUAL => Ok(Literal::Boolean(!self.is_equals(left, right))),
+            TokenType::EQUAL_EQUAL => Ok(Literal::Boolean(self.is_equals(left, right))),
+            _ => Err(RuntimeError::new(
+                operator.clone(),
+                "Binary expression error",
+            )),
+        }
+    }
+
+    // Runs the methods a class defines for `operator` when an operand is an
+    // instance of it. Arithmetic calls `__add__` and the like on the left
+    // operand, or else `__radd__` and the like on the right one. Equality
+    // calls `__eq__` on either, and the orderings all come from `__lt__`:
+    // `a > b` is `b < a`, `a <= b` is `!(b < a)` and `a >= b` is `!(a < b)`.
+    // `None` means no instance is involved.
+    fn overloaded_op(
+        &mut self,
+        left: &Literal,
+        right: &Literal,
+        operator: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        if !matches!(left, Literal::Instance(_)) && !matches!(right, Literal::Instance(_)) {
+            return Ok(None);
+        }
+        let (name, reflected) = match operator.type_ {
+            TokenType::EQUAL_EQUAL => {
+                return Ok(Some(Literal::Boolean(self.equals(left, right, operator)?)))
+            }
+            TokenType::BANG_EQUAL => {
+                return Ok(Some(Literal::Boolean(!self.equals(left, right, operator)?)))
+            }
+            TokenType::LESS
+            | TokenType::GREATER
+            | TokenType::LESS_EQUAL
+            | TokenType::GREATER_EQUAL => {
+                let (first, second, negate) = match operator.type_ {
+                    TokenType::LESS => (left, right, false),
+                    TokenType::GREATER => (right, left, false),
+                    TokenType::LESS_EQUAL => (right, left, true),
+                    _ => (left, right, true),
+                };
+                return match self.less_than(first, second, operator)? {
+                    Some(less) => Ok(Some(Literal::Boolean(less != negate))),
+                    None => Err(self.missing_operator(left, "__lt__", right, "__lt__", operator)),
+                };
+            }
+            TokenType::PLUS => ("__add__", "__radd__"),
+            TokenType::MINUS => ("__sub__", "__rsub__"),
+            TokenType::STAR => ("__mul__", "__rmul__"),
+            TokenType::SLASH => ("__div__", "__rdiv__"),
+            TokenType::TILDE_SLASH => ("__floordiv__", "__rfloordiv__"),
+            TokenType::PERCENT => ("__mod__", "__rmod__"),
+            TokenType::STAR_STAR => ("__pow__", "__rpow__"),
+            _ => return Ok(None),
+        };
+        if let Some(result) = self.call_special_method(left, name, vec![right.clone()], operator)? {
+            return Ok(Some(result));
+        }
+        if let Some(result) =
+            self.call_special_method(right, reflected, vec![left.clone()], operator)?
+        {
+            return Ok(Some(result));
+        }
+        Err(self.missing_operator(left, name, right, reflected, operator))
+    }
+
+    // `a == b` with an instance involved, through whichever operand defines
+    // `__eq__`, or else by identity.
+    fn equals(
+        &mut self,
+        left: &Literal,
+        right: &Literal,
+        operator: &Token,
+    ) -> Result<bool, RuntimeError> {
+        if let Some(result) =
+            self.call_special_method(left, "__eq__", vec![right.clone()], operator)?
+        {
+            return Ok(self.is_truthy(result));
+        }
+        if let Some(result) =
+            self.call_special_method(right, "__eq__", vec![left.clone()], operator)?
+        {
+            return Ok(self.is_truthy(result));
+        }
+        Ok(self.is_equals(left.clone(), right.clone()))
+    }
+
+    // `a < b` through whichever operand defines `__lt__`. With only `b`
+    // defining it, `a < b` holds when `b` is neither less than nor equal to
+    // `a`. `None` means neither defines it.
+    fn less_than(
+        &mut self,
+        left: &Literal,
+        right: &Literal,
+        operator: &Token,
+    ) -> Result<Option<bool>, RuntimeError> {
+        if let Some(result) =
+            self.call_special_method(left, "__lt__", vec![right.clone()], operator)?
+        {
+            return Ok(Some(self.is_truthy(result)));
+        }
+        let Some(result) =
+            self.call_special_method(right, "__lt__", vec![left.clone()], operator)?
+        else {
+            return Ok(None);
+        };
+        if self.is_truthy(result) {
+            return Ok(Some(false));
+        }
+        Ok(Some(!self.equals(right, left, operator)?))
+    }
+
+    // The error for an operator that neither operand defines a method for,
+    // naming the method the instance among them lacks.
+    fn missing_operator(
+        &self,
+        left: &Literal,
+        name: &str,
+        right: &Literal,
+        reflected: &str,
+        operator: &Token,
+    ) -> RuntimeError {
+        let (class, name) = match (left, right) {
+            (Literal::Instance(instance), _) => (instance.class().name(), name),
+            (_, Literal::Instance(instance)) => (instance.class().name(), reflected),
+            _ => unreachable!("only called with an instance operand"),
+        };
+        RuntimeError::new(
+            operator.clone(),
+            &format!(
+                "Class '{}' doesn't define '{}' for '{}'",
+                class, name, operator.lexeme
+            ),
+        )
+    }
+
+    // Calls the method `name` on `receiver` if it is an instance of a class
+    // that has one.
+    fn call_special_method(
+        &mut self,
+        receiver: &Literal,
+        name: &str,
+        arguments: Vec<Literal>,
+        token: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let Literal::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let Some(method) = instance.class().method(name) else {
+            return Ok(None);
+        };
+        let method = method.bind(receiver.clone());
+        self.call_function(&method, arguments, vec![], token)
+            .map(Some)
+    }
+
+    // Reads the target of a compound assignment or an increment, and writes
+    // back what `update` makes of it. The target's object and index are
+    // evaluated once. Returns the old and the new value.
+    fn update_target(
+        &mut self,
+        target: &Expr,
+        operator: &Token,
+        update: &mut dyn FnMut(&mut Self, Literal) -> Result<Literal, RuntimeError>,
+    ) -> Result<(Literal, Literal), RuntimeError> {
+        match target {
+            Expr::Variable(variable) => {
+                let old = self.environment.borrow().get(variable.name().clone())?;
+                let new = update(self, old.clone())?;
+                self.environment
+                    .borrow_mut()
+                    .assign(variable.name().clone(), new.clone())?;
+                Ok((old, new))
+            }
+            Expr::Index(index) => {
+                let object = self.evaluate(index.object())?;
+                let key = self.evaluate(index.index())?;
+                let old = self.get_index(object.clone(), key.clone(), index.bracket())?;
+                let new = update(self, old.clone())?;
+                self.set_index(object, key, new.clone(), index.bracket())?;
+                Ok((old, new))
+            }
+            Expr::Get(get) => {
+                let object = self.evaluate(get.object())?;
+                let old = self.get_property(object.clone(), get.name())?;
+                let new = update(self, old.clone())?;
+                self.set_property(object, get.name(), new.clone())?;
+                Ok((old, new))
+            }
+            _ => Err(RuntimeError::new(
+                operator.clone(),
+                "Invalid assignment target",
+            )),
+        }
+    }
+
+    // The binary operator behind `+=` or `++`, keeping the position of the
+    // original token for errors.
+    fn update_operator(&self, operator: &Token) -> Token {
+        let type_ = match operator.type_ {
+            TokenType::PLUS_EQUAL | TokenType::PLUS_PLUS => TokenType::PLUS,
+            TokenType::MINUS_EQUAL | TokenType::MINUS_MINUS => TokenType::MINUS,
+            TokenType::STAR_EQUAL => TokenType::STAR,
+            TokenType::SLASH_EQUAL => TokenType::SLASH,
+            _ => TokenType::PERCENT,
+        };
+        Token::new(
+            type_,
+            operator.lexeme[..1].to_string(),
+            Literal::Null,
+            operator.line,
+            operator.column,
+        )
+    }
+
+    fn is_truthy(&self, literal: Literal) -> bool {
+        match literal {
+            Literal::Null => false,
+            Literal::Boolean(value) => value,
+            _ => true,
+        }
+    }
+
+    fn is_number(&self, value: &Literal) -> bool {
+        matches!(
+            value,
+            Literal::Number(_) | Literal::Integer(_) | Literal::BigInt(_) | Literal::Decimal(_)
+        )
+    }
+
+    // Brings two numbers to a common type. Integers widen to big integers,
+    // then to decimals or floats, but decimals and floats don't mix, as that
+    // would quietly lose the exactness of the decimal.
+    fn numeric_operands(
+        &self,
+        left: &Literal,
+        right: &Literal,
+        op_name: &str,
+        token: &Token,
+    ) -> Result<Operands, RuntimeError> {
+        let operands = match (left, right) {
+            (Literal::Integer(l), Literal::Integer(r)) => Operands::Integers(*l, *r),
+            (Literal::Decimal(_), Literal::Number(_))
+            | (Literal::Number(_), Literal::Decimal(_)) => {
+                return Err(RuntimeError::new(
+                    token.clone(),
+                    "Can't mix decimal and float numbers",
+                ))
+            }
+            (Literal::Decimal(_), _) | (_, Literal::Decimal(_)) => {
+                match (self.as_decimal(left), self.as_decimal(right)) {
+                    (Some(l), Some(r)) => Operands::Decimals(l, r),
+                    _ => return Err(self.numbers_required(op_name, token)),
+                }
+            }
+            (Literal::Number(_), _) | (_, Literal::Number(_)) => {
+                match (self.as_float(left), self.as_float(right)) {
+                    (Some(l), Some(r)) => Operands::Floats(l, r),
+                    _ => return Err(self.numbers_required(op_name, token)),
+                }
+            }
+            _ => match (self.as_big_int(left), self.as_big_int(right)) {
+                (Some(l), Some(r)) => Operands::BigInts(l, r),
+                _ => return Err(self.numbers_required(op_name, token)),
+            },
+        };
+        Ok(operands)
+    }
+
+    fn numbers_required(&self, op_name: &str, token: &Token) -> RuntimeError {
+        RuntimeError::new(
+            token.clone(),
+            &format!("Operator '{}' requires two numbers", op_name),
+        )
+    }
+
+    fn as_big_int(&self, value: &Literal) -> Option<BigInt> {
+        match value {
+            Literal::Integer(integer) => Some(BigInt::from_i64(*integer)),
+            Literal::BigInt(big_int) => Some(big_int.clone()),
+            _ => None,
+        }
+    }
+
+    fn as_decimal(&self, value: &Literal) -> Option<Decimal> {
+        match value {
+            Literal::Decimal(decimal) => Some(decimal.clone()),
+            _ => Some(Decimal::new(self.as_big_int(value)?, 0)),
+        }
+    }
+
+    fn as_float(&self, value: &Literal) -> Option<f64> {
+        match value {
+            Literal::Number(number) => Some(*number),
+            Literal::Integer(integer) => Some(*integer as f64),
+            Literal::BigInt(big_int) => Some(big_int.to_f64()),
+            _ => None,
+        }
+    }
+
+    // Integers overflowing an i64 are redone as big integers. Plain division
+    // of integers yields a float, while `~/` and `%` truncate.
+    fn eval_number_binary_op(
+        &self,
+        left: Literal,
+        right: Literal,
+        token: Token,
+    ) -> Result<Literal, RuntimeError> {
+        let op = token.type_;
+        let division_by_zero = || RuntimeError::new(token.clone(), "Division by zero");
+
+        match self.numeric_operands(&left, &right, &token.lexeme, &token)? {
+            Operands::Integers(l, r) if op == TokenType::SLASH => {
+                Ok(Literal::Number(l as f64 / r as f64))
+            }
+            Operands::Integers(l, r) => {
+                let result = match op {
+                    TokenType::PLUS => l.checked_add(r),
+                    TokenType::MINUS => l.checked_sub(r),
+                    TokenType::STAR => l.checked_mul(r),
+                    TokenType::TILDE_SLASH => l.checked_div(r),
+                    _ => l.checked_rem(r),
+                };
+                match result {
+                    Some(result) => Ok(Literal::Integer(result)),
+                    None if r == 0 => Err(division_by_zero()),
+                    None => self.eval_number_binary_op(
+                        Literal::BigInt(BigInt::from_i64(l)),
+                        Literal::BigInt(BigInt::from_i64(r)),
+                        token,
+                    ),
+                }
+            }
+            Operands::BigInts(l, r) => Ok(match op {
+                TokenType::PLUS => Literal::integer(l.add(&r)),
+                TokenType::MINUS => Literal::integer(l.sub(&r)),
+                TokenType::STAR => Literal::integer(l.mul(&r)),
+                TokenType::SLASH => Literal::Number(l.to_f64() / r.to_f64()),
+                TokenType::TILDE_SLASH => {
+                    Literal::integer(l.div_rem(&r).ok_or_else(division_by_zero)?.0)
+                }
+                _ => Literal::integer(l.div_rem(&r).ok_or_else(division_by_zero)?.1),
+            }),
+            Operands::Decimals(l, r) => Ok(Literal::Decimal(match op {
+                TokenType::PLUS => l.add(&r),
+                TokenType::MINUS => l.sub(&r),
+                TokenType::STAR => l.mul(&r),
+                TokenType::SLASH => l.div(&r).ok_or_else(division_by_zero)?,
+                TokenType::TILDE_SLASH => l.div_trunc(&r).ok_or_else(division_by_zero)?,
+                _ => l.rem(&r).ok_or_else(division_by_zero)?,
+            })),
+            Operands::Floats(l, r) => Ok(Literal::Number(match op {
+                TokenType::PLUS => l + r,
+                TokenType::MINUS => l - r,
+                TokenType::STAR => l * r,
+                TokenType::SLASH => l / r,
+                TokenType::TILDE_SLASH => (l / r).trunc(),
+                _ => l % r,
+            })),
+        }
+    }
+
+    // A negative integer exponent gives a float, like plain division does.
+    fn eval_power_op(
+        &self,
+        left: Literal,
+        right: Literal,
+        token: Token,
+    ) -> Result<Literal, RuntimeError> {
+        match self.numeric_operands(&left, &right, "**", &token)? {
+            Operands::Integers(l, r) if r < 0 => Ok(Literal::Number((l as f64).powf(r as f64))),
+            Operands::Integers(l, r) => {
+                let base = (l.unsigned_abs() as f64).log2();
+                let exponent = self.exponent(&BigInt::from_i64(r), base, &token)?;
+                Ok(match l.checked_pow(exponent) {
+                    Some(result) => Literal::Integer(result),
+                    None => Literal::integer(BigInt::from_i64(l).pow(exponent)),
+                })
+            }
+            Operands::BigInts(l, r) if r.is_negative() => {
+                Ok(Literal::Number(l.to_f64().powf(r.to_f64())))
+            }
+            Operands::BigInts(l, r) => {
+                let exponent = self.exponent(&r, l.log2(), &token)?;
+                Ok(Literal::integer(l.pow(exponent)))
+            }
+            Operands::Decimals(l, r) => {
+                let exponent = match r.to_big_int() {
+                    Some(exponent) if !exponent.is_negative() => {
+                        self.exponent(&exponent, l.unscaled().log2(), &token)?
+                    }
+                    _ => {
+                        return Err(RuntimeError::new(
+                            token,
+                            "Decimal exponents must be non-negative integers",
+                        ))
+                    }
+                };
+                l.pow(exponent)
+                    .map(Literal::Decimal)
+                    .ok_or_else(|| RuntimeError::new(token, "Exponent is too large"))
+            }
+            Operands::Floats(l, r) => Ok(Literal::Number(l.powf(r))),
+        }
+    }
+
+    // The exponent of `**` as a u32, if raising a base whose magnitude has
+    // the base-2 logarithm `base` to it stays within `MAX_RESULT_BITS`.
+    fn exponent(&self, exponent: &BigInt, base: f64, token: &Token) -> Result<u32, RuntimeError> {
+        exponent
+            .to_i64()
+            .and_then(|exponent| u32::try_from(exponent).ok())
+            .filter(|exponent| base <= 0.0 || base * *exponent as f64 <= MAX_RESULT_BITS)
+            .ok_or_else(|| RuntimeError::new(token.clone(), "Exponent is too large"))
+    }
+
+    // Bitwise operators work on the two's complement of integers of any
+    // size, with results that fit in an i64 staying `Integer`. Shifting left
+    // grows into a big integer like multiplying does, while shifting right
+    // rounds toward negative infinity.
+    fn eval_integer_binary_op(
+        &self,
+        left: Literal,
+        right: Literal,
+        token: Token,
+    ) -> Result<Literal, RuntimeError> {
+        if let (Literal::Integer(l), Literal::Integer(r)) = (&left, &right) {
+            match token.type_ {
+                TokenType::AMPERSAND => return Ok(Literal::Integer(l & r)),
+                TokenType::PIPE => return Ok(Literal::Integer(l | r)),
+                TokenType::CARET => return Ok(Literal::Integer(l ^ r)),
+                _ => {}
+            }
+        }
+
+        let (l, r) = match (self.as_big_int(&left), self.as_big_int(&right)) {
+            (Some(l), Some(r)) => (l, r),
+            _ => {
+                return Err(RuntimeError::new(
+                    token.clone(),
+                    &format!("Operands of '{}' must be integers", token.lexeme),
+                ))
+            }
+        };
+
+        let shift = match token.type_ {
+            TokenType::AMPERSAND => return Ok(Literal::integer(l.and(&r))),
+            TokenType::PIPE => return Ok(Literal::integer(l.or(&r))),
+            TokenType::CARET => return Ok(Literal::integer(l.xor(&r))),
+            _ if r.is_negative() => {
+                return Err(RuntimeError::new(
+                    token.clone(),
+                    "Shift count must not be negative",
+                ))
+            }
+            _ => r.to_i64().and_then(|r| u32::try_from(r).ok()).filter(|r| {
+                token.type_ != TokenType::LESS_LESS || l.log2() + *r as f64 <= MAX_RESULT_BITS
+            }),
+        };
+
+        match (token.type_, &left, shift) {
+            (TokenType::LESS_LESS, Literal::Integer(l), Some(shift)) if shift < 63 => {
+                Ok(match l.checked_mul(1 << shift) {
+                    Some(product) => Literal::Integer(product),
+                    None => Literal::integer(BigInt::from_i64(*l).shl(shift)),
+                })
+            }
+            (TokenType::LESS_LESS, _, Some(shift)) => Ok(Literal::integer(l.shl(shift))),
+            (TokenType::LESS_LESS, _, None) => {
+                Err(RuntimeError::new(token.clone(), "Shift count is too large"))
+            }
+            (_, Literal::Integer(l), shift) => {
+                Ok(Literal::Integer(l >> shift.unwrap_or(63).min(63)))
+            }
+            (_, _, Some(shift)) => Ok(Literal::integer(l.shr(shift))),
+            // Shifting out every bit leaves only the sign.
+            (_, _, None) => Ok(Literal::Integer(if l.is_negative() { -1 } else { 0 })),
+        }
+    }
+
+    fn eval_comparison_binary_op<F: Fn(Ordering) -> bool>(
+        &self,
+        left: Literal,
+        right: Literal,
+        op: F,
+        op_name: &str,
+        token: Token,
+    ) -> Result<Literal, RuntimeError> {
+        let ordering = match self.numeric_operands(&left, &right, op_name, &token)? {
+            Operands::Integers(left, right) => Some(left.cmp(&right)),
+            Operands::BigInts(left, right) => Some(left.cmp(&right)),
+            Operands::Decimals(left, right) => Some(left.cmp(&right)),
+            Operands::Floats(l, r) => match (&left, &right) {
+                (Literal::Number(_), Literal::Number(_)) => l.partial_cmp(&r),
+                _ => self.exact_ordering(&left, &right),
+            },
+        };
+        // NaN is unordered, so every comparison against it is false.
+        Ok(Literal::Boolean(ordering.is_some_and(op)))
+    }
+
+    // Orders an integer against a float by their exact values, the way
+    // `Literal::eq` compares them; going through f64 would round the integer.
+    fn exact_ordering(&self, left: &Literal, right: &Literal) -> Option<Ordering> {
+        let exact = |value: &Literal| match value {
+            Literal::Number(number) => Decimal::from_f64(*number),
+            _ => self.as_decimal(value),
+        };
+        match (exact(left), exact(right)) {
+            (Some(left), Some(right)) => Some(left.cmp(&right)),
+            // Infinities are beyond every integer and NaN is unordered.
+            _ => self.as_float(left)?.partial_cmp(&self.as_float(right)?),
+        }
+    }
+
+    fn is_equals(&self, left: Literal, right: Literal) -> bool {
+        if left == Literal::Null && right == Literal::Null {
+            return true;
+        }
+
+        if left == Literal::Null {
+            return false;
+        }
+
+        left.eq(&right)
+    }
+
+    // How `print` and interpolation show a value. An instance whose class
+    // defines `__str__` is shown as what that returns.
+    fn stringify(&mut self, value: Literal, token: &Token) -> Result<String, RuntimeError> {
+        match value {
+            Literal::Null => return Ok(String::from("nil")),
+            // Whole floats keep their ".0" so they read differently from integers.
+            Literal::Number(value) if value.fract() == 0.0 => {
+                return Ok(format!("{:.1}", value));
+            }
+            Literal::Number(value) => return Ok(value.to_string()),
+            Literal::Integer(value) => return Ok(value.to_string()),
+            Literal::BigInt(value) => return Ok(value.to_string()),
+            Literal::Decimal(value) => return Ok(value.to_string()),
+            Literal::Function(function) => return Ok(function.to_string()),
+            Literal::Enum(enum_) => return Ok(enum_.to_string()),
+            Literal::Generator(generator) => return Ok(generator.to_string()),
+            Literal::Class(class) => return Ok(class.to_string()),
+            Literal::Trait(trait_) => return Ok(trait_.to_string()),
+            Literal::Native(native) => return Ok(native.to_string()),
+            Literal::Instance(ref instance) => {
+                let text = instance.to_string();
+                return match self.call_special_method(&value, "__str__", vec![], token)? {
+                    None => Ok(text),
+                    Some(Literal::String(text)) => Ok(text),
+                    Some(_) => Err(RuntimeError::new(
+                        token.clone(),
+                        "__str__ must return a string",
+                    )),
+                };
+            }
+            Literal::EnumValue(value) => {
+                let name = format!("{}.{}", value.enum_().name(), value.variant_name());
+                if value.values().is_empty() {
+                    return Ok(name);
+                }
+                let values = self.stringify_all(value.values(), token)?;
+                return Ok(format!("{}({})", name, values.join(", ")));
+            }
+            Literal::String(str) => {
+                return Ok(str);
+            }
+            Literal::Boolean(bool) => return Ok(bool.to_string()),
+            Literal::List(list) => {
+                let pointer = Rc::as_ptr(&list).cast();
+                if self.printing.contains(&pointer) {
+                    return Ok(String::from("[...]"));
+                }
+                // A `__str__` may change the list, so it isn't kept borrowed.
+                let list = list.borrow().clone();
+                self.printing.push(pointer);
+                let elements = self.stringify_all(&list, token);
+                self.printing.pop();
+                return Ok(format!("[{}]", elements?.join(", ")));
+            }
+            Literal::Tuple(tuple) => {
+                let elements = self.stringify_all(&tuple, token)?;
+                // A trailing comma tells `(1,)` apart from a grouped `1`.
+                if elements.len() == 1 {
+                    return Ok(format!("({},)", elements[0]));
+                }
+                return Ok(format!("({})", elements.join(", ")));
+            }
+            Literal::Map(map) => {
+                let pointer = Rc::as_ptr(&map).cast();
+                if self.printing.contains(&pointer) {
+                    return Ok(String::from("{...}"));
+                }
+                let entries = map.borrow().entries().clone();
+                self.printing.push(pointer);
+                let texts = self.stringify_entries(&entries, token);
+                self.printing.pop();
+                return Ok(format!("{{{}}}", texts?.join(", ")));
+            }
+        }
+    }
+
+    fn stringify_entries(
+        &mut self,
+        entries: &[(Literal, Literal)],
+        token: &Token,
+    ) -> Result<Vec<String>, RuntimeError> {
+        entries
+            .iter()
+            .map(|(key, value)| {
+                let key = self.stringify(key.clone(), token)?;
+                let value = self.stringify(value.clone(), token)?;
+                Ok(format!("{}: {}", key, value))
+            })
+            .collect()
+    }
+
+    fn stringify_all(
+        &mut self,
+        values: &[Literal],
+        token: &Token,
+    ) -> Result<Vec<String>, RuntimeError> {
+        values
+            .iter()
+            .map(|value| self.stringify(value.clone(), token))
+            .collect()
+    }
+
+    // Whether `value` has the shape of `pattern`, collecting what the names
+    // in it bind. Unlike `destructure`, a mismatch is not an error.
+    fn matches(
+        &mut self,
+        pattern: &Pattern,
+        value: &Literal,
+        bindings: &mut Vec<(Token, Literal)>,
+    ) -> Result<bool, RuntimeError> {
+        match (pattern, value) {
+            (Pattern::Name(name), value) => bindings.push((name.clone(), value.clone())),
+            (Pattern::Wildcard(_), _) => {}
+            (
+                Pattern::Literal {
+                    value: expected, ..
+                },
+                value,
+            ) => {
+                let expected = self.evaluate(expected)?;
+                return Ok(self.is_equals(expected, value.clone()));
+            }
+            (Pattern::List { elements, rest, .. }, Literal::List(list)) => {
+                let items = list.borrow().clone();
+                let fits = match rest {
+                    Some(_) => items.len() >= elements.len(),
+                    None => items.len() == elements.len(),
+                };
+                if !fits {
+                    return Ok(false);
+                }
+                for (element, item) in elements.iter().zip(&items) {
+                    if !self.matches(element, item, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                if let Some(rest) = rest {
+                    let rest_items = items[elements.len()..].to_vec();
+                    bindings.push((
+                        rest.clone(),
+                        Literal::List(Rc::new(RefCell::new(rest_items))),
+                    ));
+                }
+            }
+            (Pattern::Tuple { elements, .. }, Literal::Tuple(tuple)) => {
+                if tuple.len() != elements.len() {
+                    return Ok(false);
+                }
+                for (element, item) in elements.iter().zip(tuple.iter()) {
+                    if !self.matches(element, item, bindings)? {
+                        return Ok(false);
+                    }
+                }
+            }
+            (Pattern::Map { entries, .. }, Literal::Map(map)) => {
+                for (key, pattern) in entries {
+                    let item = map
+                        .borrow()
+                        .get(&Literal::String(key.lexeme.clone()))
+                        .cloned();
+                    match item {
+                        Some(item) if self.matches(pattern, &item, bindings)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+            }
+            (
+                Pattern::Variant {
+                    enum_name,
+                    variant,
+                    fields,
+                },
+                value,
+            ) => {
+                let enum_ = match self.environment.borrow().get(enum_name.clone())? {
+                    Literal::Enum(enum_) => enum_,
+                    _ => {
+                        return Err(RuntimeError::new(
+                            enum_name.clone(),
+                            &format!("'{}' is not an enum", enum_name.lexeme),
+                        ))
+                    }
+                };
+                let index = self.enum_variant(&enum_, variant)?;
+                let expected = enum_.fields(index).len();
+                if let Some(fields) = fields.as_ref().filter(|fields| fields.len() != expected) {
+                    return Err(RuntimeError::new(
+                        variant.clone(),
+                        &format!(
+                            "Variant '{}' has {} values but the pattern has {}",
+                            variant.lexeme,
+                            expected,
+                            fields.len()
+                        ),
+                    ));
+                }
+
+                let Literal::EnumValue(value) = value else {
+                    return Ok(false);
+                };
+                if !Rc::ptr_eq(value.enum_(), &enum_) || value.variant() != index {
+                    return Ok(false);
+                }
+                for (field, item) in fields.iter().flatten().zip(value.values()) {
+                    if !self.matches(field, item, bindings)? {
+                        return Ok(false);
+                    }
+                }
+            }
+            (Pattern::Instance { class, fields }, value) => {
+                let Literal::Class(expected) = self.environment.borrow().get(class.clone())? else {
+                    return Err(RuntimeError::new(
+                        class.clone(),
+                        &format!("'{}' is not a class", class.lexeme),
+                    ));
+                };
+                let Literal::Instance(instance) = value else {
+                    return Ok(false);
+                };
+                if !instance.class().inherits(&expected) {
+                    return Ok(false);
+                }
+                for (name, pattern) in fields {
+                    match instance.field(&name.lexeme) {
+                        Some(item) if self.matches(pattern, &item, bindings)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+            }
+            (Pattern::List { .. } | Pattern::Tuple { .. } | Pattern::Map { .. }, _) => {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+
+    // Runs an arm whose pattern matched, giving None when its guard fails.
+    fn run_match_arm(&mut self, arm: &MatchArm) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(guard) = arm.guard() {
+            let guard = self.evaluate(guard)?;
+            if !self.is_truthy(guard) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(self.evaluate(arm.body())?))
+    }
+
+    // Pairs each name in `pattern` with its part of `value`, failing when
+    // the value does not have the shape the pattern expects.
+    fn destructure(
+        &self,
+        pattern: &Pattern,
+        value: Literal,
+    ) -> Result<Vec<(Token, Literal)>, RuntimeError> {
+        let mut bindings = Vec::new();
+        self.destructure_into(pattern, value, &mut bindings)?;
+        Ok(bindings)
+    }
+
+    fn destructure_into(
+        &self,
+        pattern: &Pattern,
+        value: Literal,
+        bindings: &mut Vec<(Token, Literal)>,
+    ) -> Result<(), RuntimeError> {
+        match (pattern, value) {
+            (Pattern::Name(name), value) => bindings.push((name.clone(), value)),
+            (
+                Pattern::List {
+                    bracket,
+                    elements,
+                    rest,
+                },
+                Literal::List(list),
+            ) => {
+                let items = list.borrow().clone();
+                let expected = match rest {
+                    Some(_) if items.len() < elements.len() => {
+                        format!("at least {}", elements.len())
+                    }
+                    None if items.len() != elements.len() => elements.len().to_string(),
+                    _ => String::new(),
+                };
+                if !expected.is_empty() {
+                    return Err(RuntimeError::new(
+                        bracket.clone(),
+                        &format!("Expected {} elements but got {}", expected, items.len()),
+                    ));
+                }
+
+                let mut items = items.into_iter();
+                for element in elements {
+                    let item = items.next().expect("the length was checked");
+                    self.destructure_into(element, item, bindings)?;
+                }
+                if let Some(rest) = rest {
+                    let rest_items = Literal::List(Rc::new(RefCell::new(items.collect())));
+                    bindings.push((rest.clone(), rest_items));
+                }
+            }
+            (Pattern::Map { entries, .. }, Literal::Map(map)) => {
+                for (key, pattern) in entries {
+                    let value = map
+                        .borrow()
+                        .get(&Literal::String(key.lexeme.clone()))
+                        .cloned();
+                    match value {
+                        Some(value) => self.destructure_into(pattern, value, bindings)?,
+                        None => {
+                            return Err(RuntimeError::new(
+                                key.clone(),
+                                &format!("Undefined key '{}'", key.lexeme),
+                            ))
+                        }
+                    }
+                }
+            }
+            (
+                Pattern::Literal { .. }
+                | Pattern::Wildcard(_)
+                | Pattern::Variant { .. }
+                | Pattern::Instance { .. },
+                _,
+            ) => {
+                unreachable!("only match arms use literal, wildcard, variant and class patterns")
+            }
+            (Pattern::List { bracket, .. }, _) => {
+                return Err(RuntimeError::new(
+                    bracket.clone(),
+                    "Only lists can be unpacked into a list pattern",
+                ))
+            }
+            (Pattern::Tuple { paren, elements }, Literal::Tuple(tuple)) => {
+                if tuple.len() != elements.len() {
+                    return Err(RuntimeError::new(
+                        paren.clone(),
+                        &format!(
+                            "Expected {} elements but got {}",
+                            elements.len(),
+                            tuple.len()
+                        ),
+                    ));
+                }
+                for (element, item) in elements.iter().zip(tuple.iter()) {
+                    self.destructure_into(element, item.clone(), bindings)?;
+                }
+            }
+            (Pattern::Tuple { paren, .. }, _) => {
+                return Err(RuntimeError::new(
+                    paren.clone(),
+                    "Only tuples can be unpacked into a tuple pattern",
+                ))
+            }
+            (Pattern::Map { brace, .. }, _) => {
+                return Err(RuntimeError::new(
+                    brace.clone(),
+                    "Only maps can be unpacked into a map pattern",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn call_function(
+        &mut self,
+        function: &Function,
+        arguments: Vec<Literal>,
+        named: Vec<(Token, Literal)>,
+        paren: &Token,
+    ) -> Result<Literal, RuntimeError> {
+        let params = function.params();
+        let variadic = params.last().filter(|param| param.variadic());
+        let fixed = &params[..params.len() - variadic.is_some() as usize];
+
+        if variadic.is_none() && arguments.len() > fixed.len() {
+            let expected = match fixed.iter().any(|param| param.default().is_some()) {
+                true => format!("at most {}", fixed.len()),
+                false => fixed.len().to_string(),
+            };
+            return Err(RuntimeError::new(
+                paren.clone(),
+                &format!(
+                    "Expected {} arguments but got {}",
+                    expected,
+                    arguments.len()
+                ),
+            ));
+        }
+
+        // Positional arguments fill the parameters in order, any left over go
+        // to the variadic one, and named ones fill what remains.
+        let mut values: Vec<Option<Literal>> = vec![None; fixed.len()];
+        let mut rest = Vec::new();
+        for (index, argument) in arguments.into_iter().enumerate() {
+            match values.get_mut(index) {
+                Some(value) => *value = Some(argument),
+                None => rest.push(argument),
+            }
+        }
+        for (name, argument) in named {
+            match fixed.iter().position(|param| {
+                param
+                    .name()
+                    .is_some_and(|param| param.lexeme == name.lexeme)
+            }) {
+                Some(index) if values[index].is_some() => {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        &format!("Argument '{}' was given more than once", name.lexeme),
+                    ))
+                }
+                Some(index) => values[index] = Some(argument),
+                None => {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        &format!("Unknown argument '{}'", name.lexeme),
+                    ))
+                }
+            }
+        }
+
+        let environment = Rc::new(RefCell::new(Environment::new_enclosed(
+            function.closure().clone(),
+        )));
+        for (index, (param, value)) in fixed.iter().zip(values).enumerate() {
+            let value = match (value, param.default()) {
+                (Some(value), _) => value,
+                // Evaluated in the call's scope, so it sees the parameters
+                // before it.
+                (None, Some(default)) => {
+                    let previous = std::mem::replace(&mut self.environment, environment.clone());
+                    let value = self.evaluate(default);
+                    self.environment = previous;
+                    value?
+                }
+                (None, None) => {
+                    let message = match param.name() {
+                        Some(name) => format!("Missing argument '{}'", name.lexeme),
+                        None => format!("Missing argument {}", index + 1),
+                    };
+                    return Err(RuntimeError::new(paren.clone(), &message));
+                }
+            };
+            for (name, value) in self.destructure(param.pattern(), value)? {
+                environment.borrow_mut().define(&name.lexeme, value);
+            }
+        }
+        if let Some(variadic) = variadic {
+            environment.borrow_mut().define(
+                &variadic.pattern().token().lexeme,
+                Literal::List(Rc::new(RefCell::new(rest))),
+            );
+        }
+
+        // The body of a generator only runs as its values are asked for.
+        if function.is_generator() {
+            let generator = Generator::new(
+                function.name().clone(),
+                function.body().clone(),
+                environment,
+            );
+            return Ok(Literal::Generator(Rc::new(generator)));
+        }
+
+        let value = match self.execute_block(function.body().clone(), environment) {
+            Ok(()) => Literal::Null,
+            Err(Unwind::Return(value)) => value,
+            Err(Unwind::Error(error)) => return Err(error),
+            // The parser keeps `break` and `continue` from leaving a function.
+            Err(Unwind::Break(_)) | Err(Unwind::Continue(_)) => Literal::Null,
+        };
+        // `init` gives back its instance, also when it is called again.
+        if function.is_initializer() {
+            let this = function.closure().borrow().values.get("this").cloned();
+            return Ok(this.unwrap_or(Literal::Null));
+        }
+        Ok(value)
+    }
+
+    fn call_method(
+        &mut self,
+        object: Literal,
+        name: &Token,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal, RuntimeError> {
+        match object {
+            Literal::List(list) => self.call_list_method(list, name, arguments),
+            Literal::Map(map) => self.call_map_method(map, name, arguments),
+            Literal::Decimal(decimal) => self.call_decimal_method(decimal, name, arguments),
+            // `next()` gives nil once the generator has finished.
+            Literal::Generator(generator) => match (name.lexeme.as_str(), arguments.as_slice()) {
+                ("next", []) => Ok(self.resume(&generator, name)?.unwrap_or(Literal::Null)),
+                ("next", _) => Err(RuntimeError::new(
+                    name.clone(),
+                    &format!("Wrong number of arguments to '{}'", name.lexeme),
+                )),
+                _ => Err(RuntimeError::new(
+                    name.clone(),
+                    &format!("Undefined generator method '{}'", name.lexeme),
+                )),
+            },
+            Literal::Enum(enum_) => {
+                let variant = self.enum_variant(&enum_, name)?;
+                let fields = enum_.fields(variant).len();
+                if arguments.len() != fields {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        &format!("Expected {} values but got {}", fields, arguments.len()),
+                    ));
+                }
+                Ok(Literal::EnumValue(Rc::new(EnumValue::new(
+                    enum_, variant, arguments,
+                ))))
+            }
+            _ => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined method '{}'", name.lexeme),
+            )),
+        }
+    }
+
+    fn enum_variant(&self, enum_: &Enum, name: &Token) -> Result<usize, RuntimeError> {
+        enum_.variant(&name.lexeme).ok_or_else(|| {
+            RuntimeError::new(
+                name.clone(),
+                &format!("Undefined variant '{}.{}'", enum_.name(), name.lexeme),
+            )
+        })
+    }
+
+    fn call_list_method(
+        &mut self,
+        list: Rc<RefCell<Vec<Literal>>>,
+        name: &Token,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal, RuntimeError> {
+        let len = list.borrow().len();
+        match (name.lexeme.as_str(), arguments.as_slice()) {
+            ("push", [value]) => {
+                list.borrow_mut().push(value.clone());
+                Ok(Literal::Null)
+            }
+            ("pop", []) => list
+                .borrow_mut()
+                .pop()
+                .ok_or_else(|| RuntimeError::new(name.clone(), "Can't pop from an empty list")),
+            ("len", []) => Ok(Literal::Integer(len as i64)),
+            ("slice", [start]) => {
+                let start = self.slice_bound(start, len, name)?;
+                let elements = list.borrow()[start..].to_vec();
+                Ok(Literal::List(Rc::new(RefCell::new(elements))))
+            }
+            ("slice", [start, end]) => {
+                let start = self.slice_bound(start, len, name)?;
+                let end = self.slice_bound(end, len, name)?;
+                if start > end {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        "Slice start must not be after its end",
+                    ));
+                }
+                let elements = list.borrow()[start..end].to_vec();
+                Ok(Literal::List(Rc::new(RefCell::new(elements))))
+            }
+            ("map", [Literal::Function(function)]) => {
+                let elements = list.borrow().clone();
+                let mut mapped = Vec::with_capacity(elements.len());
+                for element in elements {
+                    mapped.push(self.call_function(function, vec![element], vec![], name)?);
+                }
+                Ok(Literal::List(Rc::new(RefCell::new(mapped))))
+            }
+            ("filter", [Literal::Function(function)]) => {
+                let elements = list.borrow().clone();
+                let mut kept = Vec::new();
+                for element in elements {
+                    let keep = self.call_function(function, vec![element.clone()], vec![], name)?;
+                    if self.is_truthy(keep) {
+                        kept.push(element);
+                    }
+                }
+                Ok(Literal::List(Rc::new(RefCell::new(kept))))
+            }
+            ("map" | "filter", [_]) => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Argument to '{}' must be a function", name.lexeme),
+            )),
+            ("push" | "pop" | "len" | "slice" | "map" | "filter", _) => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Wrong number of arguments to '{}'", name.lexeme),
+            )),
+            _ => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined list method '{}'", name.lexeme),
+            )),
+        }
+    }
+
+    fn call_map_method(
+        &mut self,
+        map: Rc<RefCell<Map>>,
+        name: &Token,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal, RuntimeError> {
+        match (name.lexeme.as_str(), arguments.as_slice()) {
+            ("keys", []) => {
+                let keys = map
+                    .borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                Ok(Literal::List(Rc::new(RefCell::new(keys))))
+            }
+            ("values", []) => {
+                let values = map
+                    .borrow()
+                    .entries()
+                    .iter()
+                    .map(|(_, value)| value.clone())
+                    .collect();
+                Ok(Literal::List(Rc::new(RefCell::new(values))))
+            }
+            ("has", [key]) => Ok(Literal::Boolean(map.borrow().contains_key(key))),
+            ("remove", [key]) => Ok(map.borrow_mut().remove(key).unwrap_or(Literal::Null)),
+            ("len", []) => Ok(Literal::Integer(map.borrow().len() as i64)),
+            ("keys" | "values" | "has" | "remove" | "len", _) => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Wrong number of arguments to '{}'", name.lexeme),
+            )),
+            _ => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined map method '{}'", name.lexeme),
+            )),
+        }
+    }
+
+    fn call_decimal_method(
+        &mut self,
+        decimal: Decimal,
+        name: &Token,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal, RuntimeError> {
+        match (name.lexeme.as_str(), arguments.as_slice()) {
+            ("scale", []) => Ok(Literal::Integer(decimal.scale() as i64)),
+            ("round", [scale]) => {
+                let scale = self.decimal_scale(scale, name)?;
+                Ok(Literal::Decimal(
+                    decimal.round(scale, RoundingMode::HalfEven),
+                ))
+            }
+            ("round", [scale, Literal::String(mode)]) => {
+                let scale = self.decimal_scale(scale, name)?;
+                match RoundingMode::from_name(mode) {
+                    Some(mode) => Ok(Literal::Decimal(decimal.round(scale, mode))),
+                    None => Err(RuntimeError::new(
+                        name.clone(),
+                        &format!("Unknown rounding mode '{}'", mode),
+                    )),
+                }
+            }
+            ("scale" | "round", _) => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Wrong number of arguments to '{}'", name.lexeme),
+            )),
+            _ => Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined decimal method '{}'", name.lexeme),
+            )),
+        }
+    }
+
+    fn decimal_scale(&self, scale: &Literal, token: &Token) -> Result<u32, RuntimeError> {
+        match scale {
+            Literal::Integer(scale) if (0..=MAX_DECIMAL_SCALE).contains(scale) => Ok(*scale as u32),
+            Literal::Integer(scale) if *scale > MAX_DECIMAL_SCALE => Err(RuntimeError::new(
+                token.clone(),
+                &format!("Scale can be at most {}", MAX_DECIMAL_SCALE),
+            )),
+            Literal::BigInt(scale) if !scale.is_negative() => Err(RuntimeError::new(
+                token.clone(),
+                &format!("Scale can be at most {}", MAX_DECIMAL_SCALE),
+            )),
+            _ => Err(RuntimeError::new(
+                token.clone(),
+                "Scale must be a non-negative integer",
+            )),
+        }
+    }
+
+    fn get_index(
+        &mut self,
+        object: Literal,
+        index: Literal,
+        bracket: &Token,
+    ) -> Result<Literal, RuntimeError> {
+        if let Some(value) =
+            self.call_special_method(&object, "__index__", vec![index.clone()], bracket)?
+        {
+            return Ok(value);
+        }
+        match object {
+            Literal::List(list) => {
+                let index = self.list_index(index, list.borrow().len(), bracket)?;
+                Ok(list.borrow()[index].clone())
+            }
+            Literal::Tuple(tuple) => {
+                let index = self.list_index(index, tuple.len(), bracket)?;
+                Ok(tuple[index].clone())
+            }
+            Literal::Map(map) => {
+                let value = map.borrow().get(&index).cloned();
+                match value {
+                    Some(value) => Ok(value),
+                    None => {
+                        let index = self.stringify(index, bracket)?;
+                        Err(RuntimeError::new(
+                            bracket.clone(),
+                            &format!("Undefined key '{}'", index),
+                        ))
+                    }
+                }
+            }
+            _ => Err(RuntimeError::new(
+                bracket.clone(),
+                "Only lists and maps can be indexed",
+            )),
+        }
+    }
+
+    fn set_index(
+        &self,
+        object: Literal,
+        index: Literal,
+        value: Literal,
+        bracket: &Token,
+    ) -> Result<(), RuntimeError> {
+        match object {
+            Literal::Tuple(_) => Err(RuntimeError::new(
+                bracket.clone(),
+                "Tuples can't be changed",
+            )),
+            Literal::List(list) => {
+                let index = self.list_index(index, list.borrow().len(), bracket)?;
+                list.borrow_mut()[index] = value;
+                Ok(())
+            }
+            Literal::Map(map) => {
+                let key = self.map_key(index, bracket)?;
+                map.borrow_mut().insert(key, value);
+                Ok(())
+            }
+            _ => Err(RuntimeError::new(
+                bracket.clone(),
+                "Only lists and maps can be indexed",
+            )),
+        }
+    }
+
+    fn map_key(&self, key: Literal, token: &Token) -> Result<Literal, RuntimeError> {
+        if Map::is_valid_key(&key) {
+            Ok(key)
+        } else {
+            Err(RuntimeError::new(
+                token.clone(),
+                "Map keys must be strings, numbers or booleans",
+            ))
+        }
+    }
+
+    // Checks that `index` points at an element of a list of length `len`.
+    fn list_index(&self, index: Literal, len: usize, token: &Token) -> Result<usize, RuntimeError> {
+        match index {
+            Literal::Integer(n) => {
+                if n >= 0 && (n as usize) < len {
+                    Ok(n as usize)
+                } else {
+                    Err(RuntimeError::new(
+                        token.clone(),
+                        &format!("Index {} out of bounds for list of length {}", n, len),
+                    ))
+                }
+            }
+            _ => Err(RuntimeError::new(
+                token.clone(),
+                "List index must be an integer",
+            )),
+        }
+    }
+
+    // Like `list_index`, but also accepts `len` as the end of a slice.
+    fn slice_bound(
+        &self,
+        bound: &Literal,
+        len: usize,
+        token: &Token,
+    ) -> Result<usize, RuntimeError> {
+        match bound {
+            Literal::Integer(n) if *n == len as i64 => Ok(len),
+            _ => self.list_index(bound.clone(), len, token),
+        }
+    }
+}
+
+// `implements(value, Trait)`: whether `value` is a class, or an instance of
+// one, that mixes in the trait directly or through a superclass.
+fn implements(arguments: &[Literal]) -> Result<Literal, String> {
+    let Literal::Trait(trait_) = &arguments[1] else {
+        return Err(String::from(
+            "Second argument to 'implements' must be a trait",
+        ));
+    };
+    let implements = match &arguments[0] {
+        Literal::Instance(instance) => instance.class().implements(trait_),
+        Literal::Class(class) => class.implements(trait_),
+        _ => false,
+    };
+    Ok(Literal::Boolean(implements))
 }
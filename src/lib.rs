@@ -0,0 +1,61 @@
+//! A tree-walking interpreter for Lox. Besides running scripts, the scanner
+//! can be used on its own through `lex`.
+
+mod ast_printer;
+mod big_int;
+mod class_object;
+mod decimal;
+mod dialect;
+mod enum_object;
+mod environment;
+mod expr;
+mod function_object;
+mod generator_object;
+mod interpreter;
+mod literal_object;
+mod map_object;
+mod parser;
+mod scanner;
+mod stmt;
+mod token;
+mod token_type;
+
+pub use dialect::{Dialect, Extension};
+pub use interpreter::{Interpreter, RuntimeError};
+pub use parser::Parser;
+pub use scanner::{lex, lex_with_dialect, Scanner};
+pub use token::Token;
+pub use token_type::TokenType;
+
+pub static mut HAD_ERROR: bool = false;
+pub static mut HAD_RUNTIME_ERROR: bool = false;
+
+pub fn error(line: u64, message: &str) {
+    report(line, "", message)
+}
+
+pub fn run_time_error(error: RuntimeError) {
+    println!("{} \n[line {}]", error.message, error.token.line);
+    unsafe { HAD_RUNTIME_ERROR = true }
+}
+
+fn report(line: u64, where_: &str, message: &str) {
+    println!("[line   {}  ] Error  {}  : {}", line, where_, message);
+    unsafe { HAD_ERROR = true }
+}
+
+fn token_error(token: &Token, message: &'static str) {
+    if token.type_ == TokenType::EOF {
+        report(token.line, " at end", message);
+    } else {
+        report(token.line, &format!(" at '{}'", &token.lexeme), message);
+    }
+}
+
+// Reported like an error, but the script still runs.
+fn token_warning(token: &Token, message: &'static str) {
+    println!(
+        "[line   {}  ] Warning at '{}'  : {}",
+        token.line, token.lexeme, message
+    );
+}
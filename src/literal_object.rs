@@ -1,7 +1,129 @@
-#[derive(Debug, Clone, PartialEq)]
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    big_int::BigInt,
+    class_object::{Class, Instance, Trait},
+    decimal::Decimal,
+    enum_object::{Enum, EnumValue},
+    function_object::{Function, NativeFunction},
+    generator_object::Generator,
+    map_object::Map,
+};
+
+#[derive(Debug, Clone)]
 pub enum Literal {
     String(String),
     Boolean(bool),
     Number(f64),
+    Integer(i64),
+    // Only holds integers outside the i64 range, see `Literal::integer`.
+    BigInt(BigInt),
+    Decimal(Decimal),
+    // Lists are shared by reference, so changes are visible through every copy.
+    List(Rc<RefCell<Vec<Literal>>>),
+    Map(Rc<RefCell<Map>>),
+    // Tuples can't be changed, so they compare by their elements.
+    Tuple(Rc<Vec<Literal>>),
+    // Functions compare by identity.
+    Function(Rc<Function>),
+    Native(Rc<NativeFunction>),
+    // Enums compare by identity, their values by variant and contents.
+    Enum(Rc<Enum>),
+    EnumValue(Rc<EnumValue>),
+    // Generators compare by identity, as each call runs on its own.
+    Generator(Rc<Generator>),
+    // Classes, traits and instances compare by identity.
+    Class(Rc<Class>),
+    Trait(Rc<Trait>),
+    Instance(Rc<Instance>),
     Null,
 }
+
+thread_local! {
+    // The pairs of lists or maps being compared further up the stack.
+    static COMPARING: RefCell<Vec<(*const (), *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+// Compares two lists or maps with `eq` unless the same pair is already
+// being compared further up. Then they are taken as equal, as nothing found
+// so far tells them apart, so a list or map that contains itself compares
+// without recursing forever.
+fn compare_once(left: *const (), right: *const (), eq: impl FnOnce() -> bool) -> bool {
+    let pair = (left, right);
+    if COMPARING.with(|comparing| comparing.borrow().contains(&pair)) {
+        return true;
+    }
+    COMPARING.with(|comparing| comparing.borrow_mut().push(pair));
+    let equal = eq();
+    COMPARING.with(|comparing| comparing.borrow_mut().pop());
+    equal
+}
+
+impl Literal {
+    // Integers that fit in an i64 are always `Integer`, so that every
+    // integer value has a single representation.
+    pub fn integer(value: BigInt) -> Literal {
+        match value.to_i64() {
+            Some(integer) => Literal::Integer(integer),
+            None => Literal::BigInt(value),
+        }
+    }
+}
+
+// All numbers compare by their exact value, so `1 == 1.0` and `1.5d == 1.5`,
+// while `0.1d != 0.1` as that float is not exactly one tenth.
+impl PartialEq for Literal {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Literal::String(left), Literal::String(right)) => left == right,
+            (Literal::Boolean(left), Literal::Boolean(right)) => left == right,
+            (Literal::Number(left), Literal::Number(right)) => left == right,
+            (Literal::Integer(left), Literal::Integer(right)) => left == right,
+            (Literal::Integer(integer), Literal::Number(number))
+            | (Literal::Number(number), Literal::Integer(integer)) => {
+                BigInt::from_f64(*number) == Some(BigInt::from_i64(*integer))
+            }
+            (Literal::BigInt(left), Literal::BigInt(right)) => left == right,
+            (Literal::BigInt(big_int), Literal::Number(number))
+            | (Literal::Number(number), Literal::BigInt(big_int)) => {
+                BigInt::from_f64(*number).as_ref() == Some(big_int)
+            }
+            (Literal::Decimal(left), Literal::Decimal(right)) => left == right,
+            (Literal::Decimal(decimal), Literal::Integer(integer))
+            | (Literal::Integer(integer), Literal::Decimal(decimal)) => {
+                decimal.to_big_int() == Some(BigInt::from_i64(*integer))
+            }
+            (Literal::Decimal(decimal), Literal::BigInt(big_int))
+            | (Literal::BigInt(big_int), Literal::Decimal(decimal)) => {
+                decimal.to_big_int().as_ref() == Some(big_int)
+            }
+            (Literal::Decimal(decimal), Literal::Number(number))
+            | (Literal::Number(number), Literal::Decimal(decimal)) => {
+                Decimal::from_f64(*number).as_ref() == Some(decimal)
+            }
+            (Literal::List(left), Literal::List(right)) => {
+                Rc::ptr_eq(left, right)
+                    || compare_once(Rc::as_ptr(left).cast(), Rc::as_ptr(right).cast(), || {
+                        left == right
+                    })
+            }
+            (Literal::Map(left), Literal::Map(right)) => {
+                Rc::ptr_eq(left, right)
+                    || compare_once(Rc::as_ptr(left).cast(), Rc::as_ptr(right).cast(), || {
+                        left == right
+                    })
+            }
+            (Literal::Tuple(left), Literal::Tuple(right)) => left == right,
+            (Literal::Function(left), Literal::Function(right)) => Rc::ptr_eq(left, right),
+            (Literal::Native(left), Literal::Native(right)) => Rc::ptr_eq(left, right),
+            (Literal::Enum(left), Literal::Enum(right)) => Rc::ptr_eq(left, right),
+            (Literal::EnumValue(left), Literal::EnumValue(right)) => left == right,
+            (Literal::Generator(left), Literal::Generator(right)) => Rc::ptr_eq(left, right),
+            (Literal::Class(left), Literal::Class(right)) => Rc::ptr_eq(left, right),
+            (Literal::Trait(left), Literal::Trait(right)) => Rc::ptr_eq(left, right),
+            (Literal::Instance(left), Literal::Instance(right)) => Rc::ptr_eq(left, right),
+            (Literal::Null, Literal::Null) => true,
+            _ => false,
+        }
+    }
+}
@@ -1,45 +1,65 @@
 use std::{env, fs, process::exit};
 
-use interpreter::{Interpreter, RuntimeError};
-use parser::Parser;
-use scanner::Scanner;
-use token::Token;
-use token_type::TokenType;
-
-static mut HAD_ERROR: bool = false;
-static mut HAD_RUNTIME_ERROR: bool = false;
-
-mod ast_printer;
-mod environment;
-mod expr;
-mod interpreter;
-mod literal_object;
-mod parser;
-mod scanner;
-mod stmt;
-mod token;
-mod token_type;
+use rlox::{
+    error, lex_with_dialect, Dialect, Extension, Interpreter, Parser, Scanner, HAD_ERROR,
+    HAD_RUNTIME_ERROR,
+};
 
 fn main() {
-    let args = env::args();
-    if args.len() > 2 {
-        println!("Usage: rlox [script]");
-        exit(64);
-    } else if args.len() == 2 {
-        let file_name = args.last();
-        match file_name {
-            Some(file) => run_file(&file),
-            None => {}
+    let mut dump_tokens = false;
+    let mut dialect = Dialect::default();
+    let mut scripts = Vec::new();
+
+    for arg in env::args().skip(1) {
+        if arg == "--tokens" {
+            dump_tokens = true;
+        } else if let Some(names) = arg.strip_prefix("--enable=") {
+            for name in names.split(',') {
+                match Extension::from_name(name) {
+                    Some(extension) => dialect.enable(extension),
+                    None => {
+                        println!("Unknown extension '{}'", name);
+                        exit(64);
+                    }
+                }
+            }
+        } else {
+            scripts.push(arg);
         }
-        // run_file(args.)
+    }
+
+    if scripts.len() > 1 {
+        println!("Usage: rlox [--tokens] [--enable=extension,...] [script]");
+        exit(64);
+    } else if let Some(file) = scripts.first() {
+        run_file(file, dump_tokens, dialect);
     } else {
-        run_prompt();
+        run_prompt(dump_tokens, dialect);
     }
 }
 
-fn run_file(path: &str) {
-    let file = fs::read_to_string(path).expect("Failed to read file");
-    run(&file);
+fn run_file(path: &str, dump_tokens: bool, dialect: Dialect) {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            println!("Could not read file '{}': {}", path, err);
+            exit(66);
+        }
+    };
+    let file = match String::from_utf8(bytes) {
+        Ok(file) => file,
+        Err(err) => {
+            let valid = &err.as_bytes()[..err.utf8_error().valid_up_to()];
+            let line = valid.iter().filter(|&&byte| byte == b'\n').count() as u64 + 1;
+            error(line, &format!("'{}' is not valid UTF-8", path));
+            exit(65);
+        }
+    };
+    if dump_tokens {
+        print_tokens(&file, dialect);
+    } else {
+        run(&file, dialect);
+    }
 
     unsafe {
         if HAD_ERROR {
@@ -50,8 +70,8 @@ fn run_file(path: &str) {
         }
     }
 }
-fn run(source: &str) {
-    let mut scanner = Scanner::default(source);
+fn run(source: &str, dialect: Dialect) {
+    let mut scanner = Scanner::new(source, dialect);
     let tokens = scanner.scan_tokens();
     let mut interpreter: Interpreter = Interpreter::new();
     // Todo: add lifetimes to avoid clone
@@ -59,6 +79,13 @@ fn run(source: &str) {
     let mut parser = Parser::new(tokens.clone());
     let statements = parser.parse();
 
+    // Nothing runs once an error was reported, `run_file` exits with 65.
+    unsafe {
+        if HAD_ERROR {
+            return;
+        }
+    }
+
     match statements {
         Ok(stmts) => {
             let result = interpreter.interpret(stmts);
@@ -91,7 +118,16 @@ fn run(source: &str) {
     // }
 }
 
-fn run_prompt() {
+fn print_tokens(source: &str, dialect: Dialect) {
+    for token in lex_with_dialect(source, dialect) {
+        match token.error_message() {
+            Some(message) => error(token.line, message),
+            None => println!("{}", token.to_string()),
+        }
+    }
+}
+
+fn run_prompt(dump_tokens: bool, dialect: Dialect) {
     let mut interpreter: Interpreter = Interpreter::new();
     loop {
         let mut input = String::new();
@@ -104,13 +140,26 @@ fn run_prompt() {
         }
 
         println!("Input: {}", input);
-        let mut scanner = Scanner::default(&input);
+        if dump_tokens {
+            print_tokens(&input, dialect);
+            continue;
+        }
+
+        let mut scanner = Scanner::new(&input, dialect);
         let tokens = scanner.scan_tokens();
 
         // Todo: add lifetimes to avoid clone
         let mut parser = Parser::new(tokens.clone());
         let statements = parser.parse();
 
+        // A line with errors is skipped, and the next one starts afresh.
+        unsafe {
+            if HAD_ERROR {
+                HAD_ERROR = false;
+                continue;
+            }
+        }
+
         match statements {
             Ok(stmts) => {
                 let result = interpreter.interpret(stmts);
@@ -130,24 +179,3 @@ fn run_prompt() {
         }
     }
 }
-
-pub fn error(line: u64, message: &str) {
-    report(line, "", message)
-}
-
-pub fn run_time_error(error: RuntimeError) {
-    println!("{} \n[line {}]", error.message, error.token.line);
-    unsafe { HAD_RUNTIME_ERROR = true }
-}
-
-fn report(line: u64, where_: &str, message: &str) {
-    println!("[line   {}  ] Error  {}  : {}", line, where_, message);
-}
-
-fn token_error(token: &Token, message: &'static str) {
-    if token.type_ == TokenType::EOF {
-        report(token.line, " at end", message);
-    } else {
-        report(token.line, &format!(" at '{}'", &token.lexeme), message);
-    }
-}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use crate::{big_int::BigInt, decimal::Decimal, literal_object::Literal};
+
+// Hashable form of the literals that can be used as map keys. It follows
+// `Interpreter::is_equals`: numbers are keyed by their exact value, so 1, 1.0
+// and 1d are one key, and NaN is not a key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    String(String),
+    Integer(i64),
+    BigInt(BigInt),
+    // A fractional value in its shortest exact decimal form.
+    Decimal(String),
+    // Only the infinities, every finite float has an exact decimal value.
+    Number(u64),
+    Boolean(bool),
+}
+
+/// A hash map that iterates in insertion order. Overwriting a key keeps its
+/// position, removing one keeps the order of the others.
+#[derive(Debug, Default)]
+pub struct Map {
+    entries: Vec<(Literal, Literal)>,
+    indices: HashMap<Key, usize>,
+}
+
+impl Key {
+    fn from_literal(literal: &Literal) -> Option<Key> {
+        match literal {
+            Literal::String(string) => Some(Key::String(string.clone())),
+            Literal::Boolean(boolean) => Some(Key::Boolean(*boolean)),
+            Literal::Integer(integer) => Some(Key::Integer(*integer)),
+            Literal::BigInt(big_int) => Some(Key::BigInt(big_int.clone())),
+            Literal::Decimal(decimal) => Some(Key::from_decimal(decimal)),
+            Literal::Number(number) if number.is_nan() => None,
+            Literal::Number(number) => match Decimal::from_f64(*number) {
+                Some(decimal) => Some(Key::from_decimal(&decimal)),
+                None => Some(Key::Number(number.to_bits())),
+            },
+            _ => None,
+        }
+    }
+
+    fn from_decimal(decimal: &Decimal) -> Key {
+        match decimal.to_big_int() {
+            Some(big_int) => match big_int.to_i64() {
+                Some(integer) => Key::Integer(integer),
+                None => Key::BigInt(big_int),
+            },
+            None => Key::Decimal(decimal.normalized().to_string()),
+        }
+    }
+}
+
+impl Map {
+    pub fn is_valid_key(key: &Literal) -> bool {
+        Key::from_literal(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, key: &Literal) -> Option<&Literal> {
+        let index = self.indices.get(&Key::from_literal(key)?)?;
+        Some(&self.entries[*index].1)
+    }
+
+    pub fn contains_key(&self, key: &Literal) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts or overwrites `key`, which must pass `Map::is_valid_key`.
+    pub fn insert(&mut self, key: Literal, value: Literal) {
+        let Some(hashed) = Key::from_literal(&key) else {
+            return;
+        };
+        match self.indices.get(&hashed) {
+            Some(index) => self.entries[*index].1 = value,
+            None => {
+                self.indices.insert(hashed, self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &Literal) -> Option<Literal> {
+        let index = self.indices.remove(&Key::from_literal(key)?)?;
+        let (_, value) = self.entries.remove(index);
+        for position in self.indices.values_mut() {
+            if *position > index {
+                *position -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    pub fn entries(&self) -> &Vec<(Literal, Literal)> {
+        &self.entries
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .entries
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
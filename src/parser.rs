@@ -1,9 +1,16 @@
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 
 use crate::{
-    expr::{Assign, Binary, Expr, Grouping, Literal, Unary, Variable},
+    expr::{
+        Assign, Binary, Call, Compound, Conditional, Expr, Get, Grouping, Increment, Index,
+        Interpolation, Lambda, List, Literal, Logical, Map, Match, MatchArm, Parameter, Pattern,
+        Range, Set, SetIndex, Super, This, Tuple, Unary, Variable,
+    },
     literal_object::Literal as LiteralValue,
-    stmt::{Block, Expression, Print, Stmt, Var},
+    stmt::{
+        Block, Break, Class, Continue, Enum, Expression, ForIn, Function, Print, Return, Stmt,
+        Trait, Var, Variant, Yield,
+    },
     token::Token,
     token_type::TokenType::{self, *},
 };
@@ -11,8 +18,34 @@ use crate::{
 pub struct Parser {
     pub tokens: Vec<Token>,
     pub current: u32,
+    // Labels of the loops enclosing the statement being parsed, innermost last.
+    pub loops: Vec<Option<String>>,
+    // Whether the statement being parsed is inside a function body.
+    pub in_function: bool,
+    // Names declared in each enclosing scope, innermost last, with the
+    // keyword that declared them. Parameters and functions count as `var`.
+    pub scopes: Vec<HashMap<String, TokenType>>,
+    // The class bodies enclosing the code being parsed, innermost last,
+    // which decide what `this` and `super` may refer to.
+    pub classes: Vec<ClassScope>,
+    // Where the `=>` ending the match guard being parsed is. A `(...)` right
+    // before it is the end of the guard, not the parameters of an arrow
+    // function.
+    pub guard_arrow: Option<usize>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassScope {
+    Class,
+    // A class with a superclass, where `super` can be used.
+    Subclass,
+    // A static method, which runs without an instance.
+    Static,
+}
+
+// The positional and the named arguments of a call.
+type CallArguments = (Vec<Expr>, Vec<(Token, Expr)>);
+
 #[derive(Debug)]
 pub struct ParseError {
     pub message: String,
@@ -28,10 +61,27 @@ impl ParseError {
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            loops: vec![],
+            in_function: false,
+            scopes: vec![HashMap::new()],
+            classes: vec![],
+            guard_arrow: None,
+        }
     }
 
     pub fn parse(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        // The scanner hands over its errors as tokens. They are reported
+        // here and left out of what is parsed.
+        for token in &self.tokens {
+            if let Some(message) = token.error_message() {
+                crate::error(token.line, message);
+            }
+        }
+        self.tokens.retain(|token| token.type_ != ERROR);
+
         let mut statements = Vec::new();
 
         while !self.is_at_end() {
@@ -53,8 +103,31 @@ impl Parser {
         if self.match_token(vec![PRINT]) {
             return self.print_statement();
         }
-        if self.match_token(vec![LEFT_BRACE]) {
+        if self.match_token(vec![FOR]) {
+            return self.for_statement(None);
+        }
+        if self.match_token(vec![RETURN]) {
+            return self.return_statement();
+        }
+        if self.match_token(vec![YIELD]) {
+            return self.yield_statement();
+        }
+        if self.match_token(vec![BREAK]) {
+            let label = self.loop_label()?;
+            return Ok(Stmt::Break(Rc::new(Break::new(label))));
+        }
+        if self.match_token(vec![CONTINUE]) {
+            let label = self.loop_label()?;
+            return Ok(Stmt::Continue(Rc::new(Continue::new(label))));
+        }
+        if self.check(IDENTIFIER) && self.check_next(COLON) {
+            return self.labeled_statement();
+        }
+        if self.check(LEFT_BRACE) && !self.starts_map_literal() {
+            self.advance();
+            self.scopes.push(HashMap::new());
             let statements = self.block();
+            self.scopes.pop();
             match statements {
                 Ok(statements) => return Ok(Stmt::Block(Rc::new(Block::new(statements)))),
                 Err(error) => return Err(error),
@@ -64,36 +137,671 @@ impl Parser {
         return self.expression_statement();
     }
 
+    fn function_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(IDENTIFIER, "Expect function name")?.clone();
+        // Declared first, so that the body can call the function.
+        self.declare(&name, VAR);
+        let lambda = self.function(name.clone())?;
+        Ok(Stmt::Function(Rc::new(Function::new(
+            name,
+            Rc::new(lambda),
+        ))))
+    }
+
+    // Parses the parameters and block body that follow `fun` or a function
+    // name.
+    fn function(&mut self, keyword: Token) -> Result<Lambda, ParseError> {
+        self.consume(LEFT_PAREN, "Expect '(' before parameters")?;
+        let params = self.parameters()?;
+        self.consume(LEFT_BRACE, "Expect '{' before function body")?;
+        let body = self.function_body(&params, |parser| parser.block())?;
+        Ok(Lambda::new(keyword, params, body))
+    }
+
+    // Parses the rest of `(a, b) => a + b` after its opening parenthesis.
+    fn arrow_function(&mut self) -> Result<Expr, ParseError> {
+        let keyword = self.previous().clone();
+        let params = self.parameters()?;
+        let arrow = self.consume(ARROW, "Expect '=>' after parameters")?.clone();
+        let value = self.function_body(&params, |parser| parser.expression())?;
+        let body = vec![Stmt::Return(Rc::new(Return::new(arrow, Some(value))))];
+        Ok(Expr::Lambda(Rc::new(Lambda::new(keyword, params, body))))
+    }
+
+    // Parses parameters up to and including the closing parenthesis, like
+    // `a, b = 1, ...rest`.
+    fn parameters(&mut self) -> Result<Vec<Parameter>, ParseError> {
+        let mut params: Vec<Parameter> = Vec::new();
+        if !self.check(RIGHT_PAREN) {
+            loop {
+                let variadic = self.match_token(vec![ELLIPSIS]);
+                let pattern = match variadic {
+                    true => {
+                        Pattern::Name(self.consume(IDENTIFIER, "Expect parameter name")?.clone())
+                    }
+                    false => self.pattern("Expect parameter name", false)?,
+                };
+                let mut default = None;
+                if !variadic && self.match_token(vec![EQUAL]) {
+                    default = Some(self.expression()?);
+                }
+
+                // Reported without unwinding, as the parser is not confused.
+                let names = pattern.names();
+                for (index, name) in names.iter().enumerate() {
+                    let mut earlier = params
+                        .iter()
+                        .flat_map(|other| other.pattern().names())
+                        .chain(names[..index].iter().copied());
+                    if earlier.any(|other| other.lexeme == name.lexeme) {
+                        self.error(name, "Already a parameter with this name");
+                    }
+                }
+                if params.last().is_some_and(|last| last.variadic()) {
+                    self.error(pattern.token(), "A variadic parameter must be the last one");
+                } else if !variadic
+                    && default.is_none()
+                    && params.iter().any(|other| other.default().is_some())
+                {
+                    self.error(
+                        pattern.token(),
+                        "Parameters after a default value need one too",
+                    );
+                }
+
+                params.push(Parameter::new(pattern, default, variadic));
+                if !self.match_token(vec![COMMA]) {
+                    break;
+                }
+            }
+        }
+        self.consume(RIGHT_PAREN, "Expect ')' after parameters")?;
+        Ok(params)
+    }
+
+    // Runs `parse` for a function body, where `return` is allowed and loops
+    // outside the function can't be broken out of. The body shares a scope
+    // with the parameters, as it does at runtime.
+    fn function_body<T>(
+        &mut self,
+        params: &[Parameter],
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let loops = std::mem::take(&mut self.loops);
+        let in_function = std::mem::replace(&mut self.in_function, true);
+        self.scopes.push(HashMap::new());
+        for name in params.iter().flat_map(|param| param.pattern().names()) {
+            self.declare(name, VAR);
+        }
+
+        let body = parse(self);
+
+        self.scopes.pop();
+        self.loops = loops;
+        self.in_function = in_function;
+        body
+    }
+
+    // Whether the `(` at the current token opens the parameters of an arrow
+    // function rather than a grouping.
+    fn starts_arrow_function(&self) -> bool {
+        let mut depth = 0;
+        for (offset, token) in self.tokens[self.current as usize..].iter().enumerate() {
+            match token.type_ {
+                LEFT_PAREN => depth += 1,
+                RIGHT_PAREN if depth == 1 => {
+                    let arrow = self.current as usize + offset + 1;
+                    return self.guard_arrow != Some(arrow)
+                        && self
+                            .tokens
+                            .get(arrow)
+                            .is_some_and(|next| next.type_ == ARROW);
+                }
+                RIGHT_PAREN => depth -= 1,
+                EOF => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        if !self.in_function {
+            return Err(self.error(&keyword, "Can't return from top-level code"));
+        }
+
+        let mut value = None;
+        if !self.check(SEMICOLON) {
+            value = Some(self.values()?);
+        }
+        self.consume(SEMICOLON, "Expect ';' after return value")?;
+        Ok(Stmt::Return(Rc::new(Return::new(keyword, value))))
+    }
+
+    // Parses the value of a `return` or `yield`, where `a, b` stands for the
+    // tuple `(a, b)`.
+    fn values(&mut self) -> Result<Expr, ParseError> {
+        let mut values = vec![self.expression()?];
+        while self.match_token(vec![COMMA]) {
+            values.push(self.expression()?);
+        }
+        Ok(match values.len() {
+            1 => values.remove(0),
+            _ => Expr::Tuple(Rc::new(Tuple::new(values))),
+        })
+    }
+
+    // `yield` makes the function around it a generator.
+    fn yield_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
+        if !self.in_function {
+            return Err(self.error(&keyword, "Can't yield from top-level code"));
+        }
+
+        let mut value = None;
+        if !self.check(SEMICOLON) {
+            value = Some(self.values()?);
+        }
+        self.consume(SEMICOLON, "Expect ';' after yield value")?;
+        Ok(Stmt::Yield(Rc::new(Yield::new(keyword, value))))
+    }
+
+    // Parses the rest of a `break` or `continue`, which must target an
+    // enclosing loop, optionally by its label.
+    fn loop_label(&mut self) -> Result<Option<Token>, ParseError> {
+        let keyword = self.previous().clone();
+        let mut label = None;
+        if self.match_token(vec![IDENTIFIER]) {
+            label = Some(self.previous().clone());
+        }
+
+        match label {
+            None if self.loops.is_empty() => Err(self.error(
+                &keyword,
+                "Can't use 'break' or 'continue' outside of a loop",
+            )),
+            Some(ref name)
+                if !self
+                    .loops
+                    .iter()
+                    .any(|loop_| loop_.as_deref() == Some(name.lexeme.as_str())) =>
+            {
+                Err(self.error(name, "No enclosing loop has this label"))
+            }
+            _ => {
+                self.consume(SEMICOLON, "Expect ';' after loop control statement")?;
+                Ok(label)
+            }
+        }
+    }
+
+    // A `{` in statement position opens a block, unless it is followed by a
+    // literal key and a ':', which can only start a map literal.
+    fn starts_map_literal(&self) -> bool {
+        let key = self.tokens.get(self.current as usize + 1);
+        let key_is_literal = match key {
+            Some(token) => matches!(token.type_, STRING | NUMBER | TRUE | FALSE),
+            None => false,
+        };
+        key_is_literal && self.tokens.get(self.current as usize + 2).map(|t| t.type_) == Some(COLON)
+    }
+
+    fn labeled_statement(&mut self) -> Result<Stmt, ParseError> {
+        let label = self.advance().clone();
+        self.advance();
+
+        // Labels only name loops, so that `break` and `continue` can target them.
+        if self.match_token(vec![FOR]) {
+            return self.for_statement(Some(label));
+        }
+        Err(self.error(&label, "Expect a loop after label"))
+    }
+
+    fn for_statement(&mut self, label: Option<Token>) -> Result<Stmt, ParseError> {
+        self.consume(LEFT_PAREN, "Expect '(' after 'for'")?;
+        let pattern = self.pattern("Expect loop variable name", false)?;
+        // `in` is not reserved, so it stays usable as a variable name.
+        if !(self.check(IDENTIFIER) && self.peek().lexeme == "in") {
+            return Err(self.error(self.peek(), "Expect 'in' after loop variable"));
+        }
+        self.advance();
+        let iterable = self.expression()?;
+        self.consume(RIGHT_PAREN, "Expect ')' after for clauses")?;
+
+        self.loops
+            .push(label.as_ref().map(|label| label.lexeme.clone()));
+        self.scopes.push(HashMap::new());
+        for name in pattern.names() {
+            self.declare(name, VAR);
+        }
+        let body = self.statement();
+        self.scopes.pop();
+        self.loops.pop();
+
+        Ok(Stmt::ForIn(Rc::new(ForIn::new(
+            label, pattern, iterable, body?,
+        ))))
+    }
+
     fn print_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
         match self.expression() {
             Ok(value) => {
                 self.consume(SEMICOLON, "Expect ';' after value.")?;
-                Ok(Stmt::Print(Rc::new(Print::new(value))))
+                Ok(Stmt::Print(Rc::new(Print::new(keyword, value))))
             }
             Err(error) => Err(error),
         }
     }
 
+    // Parses the rest of a `var`, `let` or `const` declaration.
     fn var_declaration(&mut self) -> Result<Stmt, ParseError> {
-        if let Ok(name) = self.consume(IDENTIFIER, "Expect variable name.") {
-            let mut initializer = None;
-            let name = name.clone();
-
-            if self.match_token(vec![EQUAL]) {
-                initializer = Some(self.expression());
-            }
-            self.consume(SEMICOLON, "Expect ';' after variable declaration")?;
-            match initializer {
-                Some(initializer) => Ok(Stmt::Var(Rc::new(Var::new(
-                    name.clone(),
-                    initializer.unwrap_or_else(|_| {
-                        Expr::Literal(Rc::new(Literal::new(LiteralValue::Null)))
-                    }),
-                )))),
-                None => Err(ParseError::new("Error when parsing".to_string())),
-            }
+        let keyword = self.previous().clone();
+        let pattern = self.pattern("Expect variable name.", false)?;
+
+        let initializer = if self.match_token(vec![EQUAL]) {
+            self.expression()?
+        } else if keyword.type_ == CONST {
+            return Err(self.error(self.peek(), "Expect '=' after constant name"));
+        } else if !matches!(pattern, Pattern::Name(_)) {
+            return Err(self.error(self.peek(), "Expect '=' after destructuring pattern"));
         } else {
-            Err(ParseError::new("Error when parsing".to_string()))
+            Expr::Literal(Rc::new(Literal::new(LiteralValue::Null)))
+        };
+        self.consume(SEMICOLON, "Expect ';' after variable declaration")?;
+
+        // Declared after the initializer, which can't see the new binding.
+        for name in pattern.names() {
+            self.declare(name, keyword.type_);
+        }
+        Ok(Stmt::Var(Rc::new(Var::new(keyword, pattern, initializer))))
+    }
+
+    // Parses the rest of `enum Color { Red, Green, Custom(r, g, b) }`.
+    fn enum_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(IDENTIFIER, "Expect enum name")?.clone();
+        self.consume(LEFT_BRACE, "Expect '{' before enum variants")?;
+
+        let mut variants: Vec<Variant> = Vec::new();
+        while !self.check(RIGHT_BRACE) {
+            let variant = self.consume(IDENTIFIER, "Expect variant name")?.clone();
+            // Reported without unwinding, as the parser is not confused.
+            if variants
+                .iter()
+                .any(|other| other.name().lexeme == variant.lexeme)
+            {
+                self.error(&variant, "Already a variant with this name");
+            }
+
+            let mut fields: Vec<Token> = Vec::new();
+            if self.match_token(vec![LEFT_PAREN]) && !self.match_token(vec![RIGHT_PAREN]) {
+                loop {
+                    let field = self.consume(IDENTIFIER, "Expect field name")?.clone();
+                    if fields.iter().any(|other| other.lexeme == field.lexeme) {
+                        self.error(&field, "Already a field with this name");
+                    }
+                    fields.push(field);
+                    if !self.match_token(vec![COMMA]) {
+                        break;
+                    }
+                }
+                self.consume(RIGHT_PAREN, "Expect ')' after variant fields")?;
+            }
+            variants.push(Variant::new(variant, fields));
+
+            if !self.match_token(vec![COMMA]) {
+                break;
+            }
+        }
+        self.consume(RIGHT_BRACE, "Expect '}' after enum variants")?;
+
+        self.declare(&name, VAR);
+        Ok(Stmt::Enum(Rc::new(Enum::new(name, variants))))
+    }
+
+    // Parses the rest of `class Name < Superclass with Trait, Other { ... }`.
+    // `with` is only special here, so it remains usable as a name.
+    fn class_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(IDENTIFIER, "Expect class name")?.clone();
+        self.declare(&name, VAR);
+
+        let mut superclass = None;
+        if self.match_token(vec![LESS]) {
+            let superclass_name = self.consume(IDENTIFIER, "Expect superclass name")?.clone();
+            if superclass_name.lexeme == name.lexeme {
+                self.error(&superclass_name, "A class can't inherit from itself");
+            }
+            superclass = Some(superclass_name);
+        }
+        let mut traits: Vec<Token> = Vec::new();
+        if self.check(IDENTIFIER) && self.peek().lexeme == "with" {
+            self.advance();
+            loop {
+                let trait_ = self.consume(IDENTIFIER, "Expect trait name")?.clone();
+                if traits.iter().any(|other| other.lexeme == trait_.lexeme) {
+                    self.error(&trait_, "Trait listed more than once");
+                }
+                traits.push(trait_);
+                if !self.match_token(vec![COMMA]) {
+                    break;
+                }
+            }
+        }
+        self.consume(LEFT_BRACE, "Expect '{' before class body")?;
+
+        self.classes.push(match superclass {
+            Some(_) => ClassScope::Subclass,
+            None => ClassScope::Class,
+        });
+        let class = self.class_body(name, superclass, traits);
+        self.classes.pop();
+        class
+    }
+
+    // Parses the rest of `trait Name { ... }`. Methods with a body are mixed
+    // into classes as they are, and those ending in `;` must be defined by
+    // every class that uses the trait.
+    fn trait_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(IDENTIFIER, "Expect trait name")?.clone();
+        self.declare(&name, VAR);
+        self.consume(LEFT_BRACE, "Expect '{' before trait body")?;
+
+        self.classes.push(ClassScope::Class);
+        let trait_ = self.trait_body(name);
+        self.classes.pop();
+        trait_
+    }
+
+    fn trait_body(&mut self, name: Token) -> Result<Stmt, ParseError> {
+        let mut methods: Vec<Rc<Function>> = Vec::new();
+        let mut required: Vec<Token> = Vec::new();
+        while !self.check(RIGHT_BRACE) && !self.is_at_end() {
+            let method = self.consume(IDENTIFIER, "Expect method name")?.clone();
+            if methods
+                .iter()
+                .map(|other| other.name())
+                .chain(required.iter())
+                .any(|other| other.lexeme == method.lexeme)
+            {
+                self.error(&method, "Already a method with this name");
+            }
+
+            self.consume(LEFT_PAREN, "Expect '(' after method name")?;
+            let params = self.parameters()?;
+            if self.match_token(vec![SEMICOLON]) {
+                required.push(method);
+                continue;
+            }
+            self.consume(LEFT_BRACE, "Expect '{' or ';' after parameters")?;
+            let body = self.function_body(&params, |parser| parser.block())?;
+            let lambda = Lambda::new(method.clone(), params, body);
+            methods.push(Rc::new(Function::new(method, Rc::new(lambda))));
+        }
+        self.consume(RIGHT_BRACE, "Expect '}' after trait body")?;
+
+        Ok(Stmt::Trait(Rc::new(Trait::new(name, methods, required))))
+    }
+
+    // Parses the members of a class up to its closing brace: methods, `get
+    // name { ... }` and `set name(value) { ... }` accessors, and `static`
+    // methods, which are called on the class itself. `get`, `set` and
+    // `static` are only special here, so they remain usable as names.
+    fn class_body(
+        &mut self,
+        name: Token,
+        superclass: Option<Token>,
+        traits: Vec<Token>,
+    ) -> Result<Stmt, ParseError> {
+        let mut methods: Vec<Rc<Function>> = Vec::new();
+        let mut getters: Vec<Rc<Function>> = Vec::new();
+        let mut setters: Vec<Rc<Function>> = Vec::new();
+        let mut statics: Vec<Rc<Function>> = Vec::new();
+
+        while !self.check(RIGHT_BRACE) && !self.is_at_end() {
+            let modifier = match self.check(IDENTIFIER) && !self.check_next(LEFT_PAREN) {
+                true => Some(self.advance().clone()),
+                false => None,
+            };
+            let modifier = match modifier {
+                Some(token) if !["get", "set", "static"].contains(&token.lexeme.as_str()) => {
+                    return Err(self.error(&token, "Expect '(' after method name"));
+                }
+                Some(token) => Some(token.lexeme),
+                None => None,
+            };
+            if modifier.as_deref() == Some("static") {
+                self.match_token(vec![FUN]);
+            }
+            let member = self.consume(IDENTIFIER, "Expect method name")?.clone();
+
+            let (function, members) = match modifier.as_deref() {
+                None => {
+                    let lambda = self.function(member.clone())?;
+                    if member.lexeme == "init" {
+                        self.check_initializer(lambda.body());
+                    }
+                    (lambda, &mut methods)
+                }
+                Some("get") => {
+                    self.consume(LEFT_BRACE, "Expect '{' before getter body")?;
+                    let body = self.function_body(&[], |parser| parser.block())?;
+                    (Lambda::new(member.clone(), vec![], body), &mut getters)
+                }
+                Some("set") => {
+                    let lambda = self.function(member.clone())?;
+                    if lambda.params().len() != 1 || lambda.params()[0].variadic() {
+                        self.error(&member, "A setter takes exactly one parameter");
+                    }
+                    (lambda, &mut setters)
+                }
+                Some("static") => {
+                    self.classes.push(ClassScope::Static);
+                    let lambda = self.function(member.clone());
+                    self.classes.pop();
+                    (lambda?, &mut statics)
+                }
+                Some(_) => unreachable!("modifiers are checked above"),
+            };
+
+            // Reported without unwinding, as the parser is not confused.
+            if members
+                .iter()
+                .any(|other| other.name().lexeme == member.lexeme)
+            {
+                self.error(&member, "Already a member with this name");
+            }
+            members.push(Rc::new(Function::new(member, Rc::new(function))));
+        }
+        self.consume(RIGHT_BRACE, "Expect '}' after class body")?;
+
+        Ok(Stmt::Class(Rc::new(Class::new(
+            name, superclass, traits, methods, getters, setters, statics,
+        ))))
+    }
+
+    // `init` always gives back the new instance, so it can't return a value.
+    // Functions declared inside it are left out, their returns are their own.
+    fn check_initializer(&self, body: &[Stmt]) {
+        for stmt in body {
+            match stmt {
+                Stmt::Return(stmt) if stmt.value().is_some() => {
+                    self.error(stmt.keyword(), "Can't return a value from an initializer");
+                }
+                Stmt::Block(block) => self.check_initializer(block.statements()),
+                Stmt::ForIn(for_in) => self.check_initializer(std::slice::from_ref(for_in.body())),
+                _ => {}
+            }
+        }
+    }
+
+    // Parses what a declaration binds: a name, or a pattern that unpacks a
+    // list or a map, like `[first, ...rest]` or `{name, age: years}`. Match
+    // arms are `refutable` and may also use literals and `_`.
+    fn pattern(&mut self, message: &'static str, refutable: bool) -> Result<Pattern, ParseError> {
+        if refutable && self.check(IDENTIFIER) && self.peek().lexeme == "_" {
+            return Ok(Pattern::Wildcard(self.advance().clone()));
+        }
+        // `Color.Red`, or `Color.Custom(r, g, b)` to match what it carries.
+        if refutable && self.check(IDENTIFIER) && self.check_next(DOT) {
+            let enum_name = self.advance().clone();
+            self.advance();
+            let variant = self
+                .consume(IDENTIFIER, "Expect variant name after '.'")?
+                .clone();
+            let mut fields = None;
+            if self.match_token(vec![LEFT_PAREN]) {
+                let mut patterns = Vec::new();
+                while !self.check(RIGHT_PAREN) {
+                    patterns.push(self.pattern(message, refutable)?);
+                    if !self.match_token(vec![COMMA]) {
+                        break;
+                    }
+                }
+                self.consume(RIGHT_PAREN, "Expect ')' after variant patterns")?;
+                fields = Some(patterns);
+            }
+            return Ok(Pattern::Variant {
+                enum_name,
+                variant,
+                fields,
+            });
+        }
+        // `Point{x, y}` matches instances of `Point` and reads their fields.
+        if refutable && self.check(IDENTIFIER) && self.check_next(LEFT_BRACE) {
+            let class = self.advance().clone();
+            self.advance();
+            let fields = self.pattern_entries(
+                message,
+                refutable,
+                "Expect field name in class pattern",
+                "Expect '}' after class pattern",
+            )?;
+            return Ok(Pattern::Instance { class, fields });
+        }
+        if refutable
+            && (matches!(self.peek().type_, NUMBER | STRING | TRUE | FALSE | NIL)
+                || self.check(MINUS) && self.check_next(NUMBER))
+        {
+            let token = self.peek().clone();
+            let value = self.unary()?;
+            return Ok(Pattern::Literal { token, value });
+        }
+
+        if self.match_token(vec![LEFT_BRACKET]) {
+            let bracket = self.previous().clone();
+            let mut elements = Vec::new();
+            let mut rest = None;
+            while !self.check(RIGHT_BRACKET) {
+                if self.match_token(vec![ELLIPSIS]) {
+                    rest = Some(self.consume(IDENTIFIER, "Expect name after '...'")?.clone());
+                    break;
+                }
+                elements.push(self.pattern(message, refutable)?);
+                if !self.match_token(vec![COMMA]) {
+                    break;
+                }
+            }
+            let message = match rest {
+                Some(_) => "Expect ']' after rest element",
+                None => "Expect ']' after list pattern",
+            };
+            self.consume(RIGHT_BRACKET, message)?;
+            return Ok(Pattern::List {
+                bracket,
+                elements,
+                rest,
+            });
+        }
+
+        // `(a, b)` unpacks a tuple, while `(a)` is just `a`.
+        if self.match_token(vec![LEFT_PAREN]) {
+            let paren = self.previous().clone();
+            let mut elements = Vec::new();
+            let mut tuple = false;
+            while !self.check(RIGHT_PAREN) {
+                elements.push(self.pattern(message, refutable)?);
+                if !self.match_token(vec![COMMA]) {
+                    break;
+                }
+                tuple = true;
+            }
+            self.consume(RIGHT_PAREN, "Expect ')' after tuple pattern")?;
+            if elements.len() == 1 && !tuple {
+                return Ok(elements.remove(0));
+            }
+            return Ok(Pattern::Tuple { paren, elements });
+        }
+
+        if self.match_token(vec![LEFT_BRACE]) {
+            let brace = self.previous().clone();
+            let entries = self.pattern_entries(
+                message,
+                refutable,
+                "Expect key in map pattern",
+                "Expect '}' after map pattern",
+            )?;
+            return Ok(Pattern::Map { brace, entries });
+        }
+
+        Ok(Pattern::Name(self.consume(IDENTIFIER, message)?.clone()))
+    }
+
+    // The `name: pattern` entries of a map or class pattern, after its `{`.
+    fn pattern_entries(
+        &mut self,
+        message: &'static str,
+        refutable: bool,
+        key_message: &'static str,
+        end_message: &'static str,
+    ) -> Result<Vec<(Token, Pattern)>, ParseError> {
+        let mut entries = Vec::new();
+        while !self.check(RIGHT_BRACE) {
+            let key = self.consume(IDENTIFIER, key_message)?.clone();
+            // `{name}` is short for `{name: name}`.
+            let pattern = match self.match_token(vec![COLON]) {
+                true => self.pattern(message, refutable)?,
+                false => Pattern::Name(key.clone()),
+            };
+            entries.push((key, pattern));
+            if !self.match_token(vec![COMMA]) {
+                break;
+            }
+        }
+        self.consume(RIGHT_BRACE, end_message)?;
+        Ok(entries)
+    }
+
+    // Records `name` in the innermost scope. A `var` may be declared again,
+    // as Lox has always allowed, but nothing may share a scope with a `let`
+    // or `const` of the same name.
+    fn declare(&mut self, name: &Token, keyword: TokenType) {
+        let scope = self.scopes.last().expect("the global scope is never left");
+        let redeclared = match scope.get(&name.lexeme) {
+            None => false,
+            Some(VAR) => keyword != VAR,
+            Some(_) => true,
+        };
+        if redeclared {
+            self.error(name, "Already a variable with this name in this scope");
+        }
+        self.scopes
+            .last_mut()
+            .expect("the global scope is never left")
+            .insert(name.lexeme.clone(), keyword);
+    }
+
+    // Rejects assigning to a name that resolves to a `const`. Names that are
+    // not declared in the source, like those from an earlier REPL line, are
+    // left to the runtime check.
+    fn check_assignable(&self, name: &Token) {
+        let keyword = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.lexeme));
+        if keyword == Some(&CONST) {
+            self.error(name, "Can't assign to a constant");
         }
     }
 
@@ -120,7 +828,22 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Expr, ParseError> {
-        let expr = self.equality();
+        let expr = self.conditional();
+
+        if self.match_token(vec![
+            PLUS_EQUAL,
+            MINUS_EQUAL,
+            STAR_EQUAL,
+            SLASH_EQUAL,
+            PERCENT_EQUAL,
+        ]) {
+            let operator = self.previous().clone();
+            let target = self.update_target(expr?, &operator, "Invalid assignment target")?;
+            let value = self.assignment()?;
+            return Ok(Expr::Compound(Rc::new(Compound::new(
+                target, operator, value,
+            ))));
+        }
 
         if self.match_token(vec![EQUAL]) {
             let equals = self.previous();
@@ -131,6 +854,7 @@ impl Parser {
                 Ok(ref expr) => match expr {
                     Expr::Variable(variable) => {
                         let name = variable.name();
+                        self.check_assignable(name);
                         match value {
                             Ok(value) => {
                                 return Ok(Expr::Assign(Rc::new(Assign::new(name.clone(), value))))
@@ -138,6 +862,21 @@ impl Parser {
                             Err(error) => return Err(error),
                         }
                     }
+                    Expr::Get(get) if !get.optional() && get.name().type_ == IDENTIFIER => {
+                        return Ok(Expr::Set(Rc::new(Set::new(
+                            get.object().clone(),
+                            get.name().clone(),
+                            value?,
+                        ))))
+                    }
+                    Expr::Index(index) => {
+                        return Ok(Expr::SetIndex(Rc::new(SetIndex::new(
+                            index.object().clone(),
+                            index.bracket().clone(),
+                            index.index().clone(),
+                            value?,
+                        ))))
+                    }
                     _ => {
                         crate::token_error(&equals, "Invalid assignment target");
                         return Err(ParseError {
@@ -161,12 +900,26 @@ impl Parser {
     }
 
     fn declaration(&mut self) -> Result<Stmt, ParseError> {
-        if self.match_token(vec![VAR]) {
+        // `fun (` starts a lambda, which is parsed as an expression statement.
+        if self.check(FUN) && self.check_next(IDENTIFIER) {
+            self.advance();
+            return self.function_declaration();
+        }
+        if self.match_token(vec![VAR, LET, CONST]) {
             match self.var_declaration() {
                 Ok(stmt) => return Ok(stmt),
                 Err(error) => return Err(error),
             }
         }
+        if self.match_token(vec![ENUM]) {
+            return self.enum_declaration();
+        }
+        if self.match_token(vec![CLASS]) {
+            return self.class_declaration();
+        }
+        if self.match_token(vec![TRAIT]) {
+            return self.trait_declaration();
+        }
 
         match self.statement() {
             Ok(stmt) => Ok(stmt),
@@ -174,17 +927,47 @@ impl Parser {
         }
     }
 
+    // `a ? b : c` binds looser than anything but assignment, and its else
+    // branch recurses so that conditionals nest to the right.
+    fn conditional(&mut self) -> Result<Expr, ParseError> {
+        let condition = self.coalesce()?;
+
+        if self.match_token(vec![QUESTION]) {
+            let then_branch = self.expression()?;
+            self.consume(
+                COLON,
+                "Expect ':' after then branch of conditional expression",
+            )?;
+            let else_branch = self.conditional()?;
+            return Ok(Expr::Conditional(Rc::new(Conditional::new(
+                condition,
+                then_branch,
+                else_branch,
+            ))));
+        }
+
+        Ok(condition)
+    }
+
+    fn coalesce(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.equality()?;
+
+        while self.match_token(vec![QUESTION_QUESTION]) {
+            let operator = self.previous().clone();
+            let right = self.equality()?;
+            expr = Expr::Logical(Rc::new(Logical::new(expr, operator, right)));
+        }
+
+        Ok(expr)
+    }
+
     fn equality(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.comparison();
 
         while self.match_token(vec![BANG_EQUAL, EQUAL_EQUAL]) {
             let operator = self.previous().clone();
             let right = self.comparison();
-            expr = Ok(Expr::Binary(Rc::new(Binary::new(
-                expr.unwrap(),
-                operator,
-                right.unwrap(),
-            ))));
+            expr = Ok(Expr::Binary(Rc::new(Binary::new(expr?, operator, right?))));
         }
 
         match expr {
@@ -196,16 +979,12 @@ impl Parser {
     }
 
     fn comparison(&mut self) -> Result<Expr, ParseError> {
-        let mut expr = self.term();
+        let mut expr = self.range();
 
         while self.match_token(vec![GREATER, GREATER_EQUAL, LESS, LESS_EQUAL]) {
             let operator = self.previous().clone();
-            let right = self.term();
-            expr = Ok(Expr::Binary(Rc::new(Binary::new(
-                expr.unwrap(),
-                operator,
-                right.unwrap(),
-            ))))
+            let right = self.range();
+            expr = Ok(Expr::Binary(Rc::new(Binary::new(expr?, operator, right?))))
         }
 
         match expr {
@@ -214,17 +993,73 @@ impl Parser {
         }
     }
 
+    fn range(&mut self) -> Result<Expr, ParseError> {
+        let start = self.bitwise_or()?;
+
+        if self.match_token(vec![DOT_DOT, DOT_DOT_EQUAL]) {
+            let operator = self.previous().clone();
+            let end = self.bitwise_or()?;
+            return Ok(Expr::Range(Rc::new(Range::new(start, operator, end))));
+        }
+
+        Ok(start)
+    }
+
+    fn bitwise_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.bitwise_xor()?;
+
+        while self.match_token(vec![PIPE]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_xor()?;
+            expr = Expr::Binary(Rc::new(Binary::new(expr, operator, right)));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.bitwise_and()?;
+
+        while self.match_token(vec![CARET]) {
+            let operator = self.previous().clone();
+            let right = self.bitwise_and()?;
+            expr = Expr::Binary(Rc::new(Binary::new(expr, operator, right)));
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.shift()?;
+
+        while self.match_token(vec![AMPERSAND]) {
+            let operator = self.previous().clone();
+            let right = self.shift()?;
+            expr = Expr::Binary(Rc::new(Binary::new(expr, operator, right)));
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.term()?;
+
+        while self.match_token(vec![LESS_LESS, GREATER_GREATER]) {
+            let operator = self.previous().clone();
+            let right = self.term()?;
+            expr = Expr::Binary(Rc::new(Binary::new(expr, operator, right)));
+        }
+
+        Ok(expr)
+    }
+
     fn term(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.factor();
 
         while self.match_token(vec![MINUS, PLUS]) {
             let operator = self.previous().clone();
             let right = self.factor();
-            expr = Ok(Expr::Binary(Rc::new(Binary::new(
-                expr.unwrap(),
-                operator,
-                right.unwrap(),
-            ))))
+            expr = Ok(Expr::Binary(Rc::new(Binary::new(expr?, operator, right?))))
         }
 
         match expr {
@@ -236,14 +1071,10 @@ impl Parser {
     fn factor(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.unary();
 
-        while self.match_token(vec![SLASH, STAR]) {
+        while self.match_token(vec![SLASH, STAR, PERCENT, TILDE_SLASH]) {
             let operator = self.previous().clone();
             let right = self.unary();
-            expr = Ok(Expr::Binary(Rc::new(Binary::new(
-                expr.unwrap(),
-                operator,
-                right.unwrap(),
-            ))));
+            expr = Ok(Expr::Binary(Rc::new(Binary::new(expr?, operator, right?))));
         }
 
         match expr {
@@ -255,13 +1086,22 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Expr, ParseError> {
-        if self.match_token(vec![BANG, MINUS]) {
+        if self.match_token(vec![PLUS_PLUS, MINUS_MINUS]) {
+            let operator = self.previous().clone();
+            let target = self.unary()?;
+            let target = self.update_target(target, &operator, "Invalid increment target")?;
+            return Ok(Expr::Increment(Rc::new(Increment::new(
+                target, operator, true,
+            ))));
+        }
+
+        if self.match_token(vec![BANG, MINUS, TILDE]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
             return Ok(Expr::Unary(Rc::new(Unary::new(operator, right))));
         }
 
-        match self.primary() {
+        match self.power() {
             Ok(expr) => Ok(expr),
             Err(_) => Err(ParseError::new(
                 "Error parsing a unary expression".to_string(),
@@ -269,6 +1109,146 @@ impl Parser {
         }
     }
 
+    // `**` binds tighter than unary minus on its left, so `-2 ** 2` is -4,
+    // and is right-associative, so `2 ** 3 ** 2` is `2 ** 9`.
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let base = self.postfix()?;
+
+        if self.match_token(vec![STAR_STAR]) {
+            let operator = self.previous().clone();
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(Rc::new(Binary::new(base, operator, exponent))));
+        }
+
+        Ok(base)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.call()?;
+
+        if self.match_token(vec![PLUS_PLUS, MINUS_MINUS]) {
+            let operator = self.previous().clone();
+            let target = self.update_target(expr, &operator, "Invalid increment target")?;
+            return Ok(Expr::Increment(Rc::new(Increment::new(
+                target, operator, false,
+            ))));
+        }
+
+        Ok(expr)
+    }
+
+    // Compound assignment and increments read and write the same place, so
+    // they take the targets a plain assignment does.
+    fn update_target(
+        &self,
+        target: Expr,
+        operator: &Token,
+        message: &'static str,
+    ) -> Result<Expr, ParseError> {
+        match target {
+            Expr::Variable(ref variable) => {
+                self.check_assignable(variable.name());
+                Ok(target)
+            }
+            Expr::Index(_) => Ok(target),
+            Expr::Get(ref get) if !get.optional() && get.name().type_ == IDENTIFIER => Ok(target),
+            _ => Err(self.error(operator, message)),
+        }
+    }
+
+    fn call(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.match_token(vec![LEFT_PAREN]) {
+                let (arguments, named) = self.call_arguments()?;
+                let paren = self.consume(RIGHT_PAREN, "Expect ')' after arguments")?;
+                expr = Expr::Call(Rc::new(Call::new(expr, paren.clone(), arguments, named)));
+            } else if self.match_token(vec![DOT, QUESTION_DOT]) {
+                let optional = self.previous().type_ == QUESTION_DOT;
+                if self.match_token(vec![NUMBER]) {
+                    expr = self.tuple_access(expr, optional)?;
+                    continue;
+                }
+                let name = self.consume(IDENTIFIER, "Expect property name after '.'")?;
+                expr = Expr::Get(Rc::new(Get::new(expr, name.clone(), optional)));
+            } else if self.match_token(vec![LEFT_BRACKET]) {
+                let bracket = self.previous().clone();
+                let index = self.expression()?;
+                self.consume(RIGHT_BRACKET, "Expect ']' after index")?;
+                expr = Expr::Index(Rc::new(Index::new(expr, bracket, index)));
+            } else {
+                break;
+            }
+        }
+
+        Ok(expr)
+    }
+
+    // `t.0` reads an element of a tuple. `t.0.1` scans as the number `0.1`,
+    // so the number is split back into one access per part.
+    fn tuple_access(&mut self, mut expr: Expr, optional: bool) -> Result<Expr, ParseError> {
+        let number = self.previous().clone();
+        for (position, part) in number.lexeme.split('.').enumerate() {
+            if part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(self.error(&number, "Expect tuple index after '.'"));
+            }
+            let index = Token::new(
+                NUMBER,
+                part.to_string(),
+                LiteralValue::Null,
+                number.line,
+                number.column,
+            );
+            expr = Expr::Get(Rc::new(Get::new(expr, index, optional && position == 0)));
+        }
+        Ok(expr)
+    }
+
+    // Like `arguments`, but also takes `name: value` arguments after the
+    // positional ones.
+    fn call_arguments(&mut self) -> Result<CallArguments, ParseError> {
+        let mut arguments = Vec::new();
+        let mut named: Vec<(Token, Expr)> = Vec::new();
+        if !self.check(RIGHT_PAREN) {
+            loop {
+                if self.check(IDENTIFIER) && self.check_next(COLON) {
+                    let name = self.advance().clone();
+                    self.advance();
+                    if named.iter().any(|(other, _)| other.lexeme == name.lexeme) {
+                        self.error(&name, "Named argument given more than once");
+                    }
+                    named.push((name, self.expression()?));
+                } else if named.is_empty() {
+                    arguments.push(self.expression()?);
+                } else {
+                    return Err(self.error(
+                        self.peek(),
+                        "Positional arguments must come before named ones",
+                    ));
+                }
+                if !self.match_token(vec![COMMA]) || self.check(RIGHT_PAREN) {
+                    break;
+                }
+            }
+        }
+        Ok((arguments, named))
+    }
+
+    // Parses comma separated expressions up to, but not including, `end`.
+    fn arguments(&mut self, end: TokenType) -> Result<Vec<Expr>, ParseError> {
+        let mut arguments = Vec::new();
+        if !self.check(end) {
+            loop {
+                arguments.push(self.expression()?);
+                if !self.match_token(vec![COMMA]) || self.check(end) {
+                    break;
+                }
+            }
+        }
+        Ok(arguments)
+    }
+
     fn primary(&mut self) -> Result<Expr, ParseError> {
         if self.match_token(vec![FALSE]) {
             return Ok(Expr::Literal(Rc::new(Literal::new(LiteralValue::Boolean(
@@ -290,14 +1270,85 @@ impl Parser {
             ))));
         }
 
+        if self.match_token(vec![INTERPOLATION]) {
+            return self.interpolation();
+        }
+
+        if self.match_token(vec![LEFT_BRACE]) {
+            return self.map();
+        }
+
+        if self.match_token(vec![MATCH]) {
+            return self.match_expression();
+        }
+
+        if self.match_token(vec![LEFT_BRACKET]) {
+            let elements = self.arguments(RIGHT_BRACKET)?;
+            self.consume(RIGHT_BRACKET, "Expect ']' after list elements")?;
+            return Ok(Expr::List(Rc::new(List::new(elements))));
+        }
+
         if self.match_token(vec![IDENTIFIER]) {
             return Ok(Expr::Variable(Rc::new(Variable::new(
                 self.previous().clone(),
             ))));
         }
 
+        if self.match_token(vec![THIS]) {
+            let keyword = self.previous().clone();
+            match self.classes.last() {
+                None => {
+                    self.error(&keyword, "Can't use 'this' outside of a class");
+                }
+                Some(ClassScope::Static) => {
+                    self.error(&keyword, "Can't use 'this' in a static method");
+                }
+                Some(_) => {}
+            }
+            return Ok(Expr::This(Rc::new(This::new(keyword))));
+        }
+
+        if self.match_token(vec![SUPER]) {
+            let keyword = self.previous().clone();
+            match self.classes.last() {
+                None => {
+                    self.error(&keyword, "Can't use 'super' outside of a class");
+                }
+                Some(ClassScope::Class) => {
+                    self.error(&keyword, "Can't use 'super' in a class with no superclass");
+                }
+                Some(ClassScope::Static) => {
+                    self.error(&keyword, "Can't use 'super' in a static method");
+                }
+                Some(ClassScope::Subclass) => {}
+            }
+            self.consume(DOT, "Expect '.' after 'super'")?;
+            let method = self
+                .consume(IDENTIFIER, "Expect superclass method name")?
+                .clone();
+            return Ok(Expr::Super(Rc::new(Super::new(keyword, method))));
+        }
+
+        if self.match_token(vec![FUN]) {
+            let keyword = self.previous().clone();
+            let lambda = self.function(keyword)?;
+            return Ok(Expr::Lambda(Rc::new(lambda)));
+        }
+
+        if self.check(LEFT_PAREN) && self.starts_arrow_function() {
+            self.advance();
+            return self.arrow_function();
+        }
+
         if self.match_token(vec![LEFT_PAREN]) {
+            if self.match_token(vec![RIGHT_PAREN]) {
+                return Ok(Expr::Tuple(Rc::new(Tuple::new(Vec::new()))));
+            }
             let expr = self.expression();
+            // A comma makes it a tuple rather than a grouping.
+            if expr.is_ok() && self.match_token(vec![COMMA]) {
+                return self.tuple(expr?);
+            }
             match self.consume(RIGHT_PAREN, "Expect ')' after expression") {
                 Ok(_) => match expr {
                     Ok(expr) => return Ok(Expr::Grouping(Rc::new(Grouping::new(expr)))),
@@ -312,6 +1363,153 @@ impl Parser {
         ))
     }
 
+    fn map(&mut self) -> Result<Expr, ParseError> {
+        let brace = self.previous().clone();
+        let mut entries = Vec::new();
+
+        while !self.check(RIGHT_BRACE) {
+            let key = self.expression()?;
+            self.consume(COLON, "Expect ':' after map key")?;
+            let value = self.expression()?;
+            entries.push((key, value));
+
+            if !self.match_token(vec![COMMA]) {
+                break;
+            }
+        }
+
+        self.consume(RIGHT_BRACE, "Expect '}' after map entries")?;
+        Ok(Expr::Map(Rc::new(Map::new(brace, entries))))
+    }
+
+    // Parses the rest of a tuple after its first element and comma. The
+    // last element may be followed by a comma, so `(a,)` has one element.
+    fn tuple(&mut self, first: Expr) -> Result<Expr, ParseError> {
+        let mut elements = vec![first];
+        while !self.check(RIGHT_PAREN) {
+            elements.push(self.expression()?);
+            if !self.match_token(vec![COMMA]) {
+                break;
+            }
+        }
+        self.consume(RIGHT_PAREN, "Expect ')' after tuple elements")?;
+        Ok(Expr::Tuple(Rc::new(Tuple::new(elements))))
+    }
+
+    // Parses the rest of `match (value) { pattern | pattern if guard => body, ... }`.
+    fn match_expression(&mut self) -> Result<Expr, ParseError> {
+        let keyword = self.previous().clone();
+        self.consume(LEFT_PAREN, "Expect '(' after 'match'")?;
+        let value = self.expression()?;
+        self.consume(RIGHT_PAREN, "Expect ')' after match value")?;
+        self.consume(LEFT_BRACE, "Expect '{' before match arms")?;
+
+        let mut arms = Vec::new();
+        while !self.check(RIGHT_BRACE) {
+            let mut patterns = vec![self.pattern("Expect pattern", true)?];
+            while self.match_token(vec![PIPE]) {
+                patterns.push(self.pattern("Expect pattern", true)?);
+            }
+            self.check_alternatives(&patterns);
+
+            // The names a pattern binds are only visible in its arm.
+            self.scopes.push(HashMap::new());
+            for name in patterns[0].names() {
+                self.declare(name, VAR);
+            }
+            let arm = self.match_arm(patterns);
+            self.scopes.pop();
+            arms.push(arm?);
+
+            if !self.match_token(vec![COMMA]) {
+                break;
+            }
+        }
+        self.consume(RIGHT_BRACE, "Expect '}' after match arms")?;
+
+        if !arms.iter().any(|arm| arm.is_catch_all()) {
+            self.warning(
+                &keyword,
+                "Match has no '_' arm, so a value may match no arm",
+            );
+        }
+        Ok(Expr::Match(Rc::new(Match::new(keyword, value, arms))))
+    }
+
+    // Parses the optional guard and the body of an arm after its patterns.
+    fn match_arm(&mut self, patterns: Vec<Pattern>) -> Result<MatchArm, ParseError> {
+        let mut guard = None;
+        if self.match_token(vec![IF]) {
+            let arrow = self.guard_arrow();
+            let outer = std::mem::replace(&mut self.guard_arrow, arrow);
+            let expr = self.expression();
+            self.guard_arrow = outer;
+            guard = Some(expr?);
+        }
+        self.consume(ARROW, "Expect '=>' after match pattern")?;
+        let body = self.expression()?;
+        Ok(MatchArm::new(patterns, guard, body))
+    }
+
+    // The first `=>` after a guard that isn't nested in brackets, which is
+    // the one that ends the guard.
+    fn guard_arrow(&self) -> Option<usize> {
+        let mut depth = 0;
+        for (offset, token) in self.tokens[self.current as usize..].iter().enumerate() {
+            match token.type_ {
+                LEFT_PAREN | LEFT_BRACKET | LEFT_BRACE => depth += 1,
+                RIGHT_PAREN | RIGHT_BRACKET | RIGHT_BRACE if depth == 0 => return None,
+                RIGHT_PAREN | RIGHT_BRACKET | RIGHT_BRACE => depth -= 1,
+                ARROW if depth == 0 => return Some(self.current as usize + offset),
+                EOF => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Alternatives share an arm, so each must bind the same names for the
+    // arm to use. Reported without unwinding.
+    fn check_alternatives(&self, patterns: &[Pattern]) {
+        let names = |pattern: &Pattern| {
+            let mut names: Vec<String> = pattern
+                .names()
+                .iter()
+                .map(|name| name.lexeme.clone())
+                .collect();
+            names.sort();
+            names
+        };
+        for alternative in &patterns[1..] {
+            if names(alternative) != names(&patterns[0]) {
+                self.error(alternative.token(), "Alternatives must bind the same names");
+            }
+        }
+    }
+
+    fn interpolation(&mut self) -> Result<Expr, ParseError> {
+        let start = self.previous().clone();
+        let mut parts = Vec::new();
+
+        loop {
+            parts.push(Expr::Literal(Rc::new(Literal::new(
+                self.previous().literal.clone(),
+            ))));
+            parts.push(self.expression()?);
+
+            if !self.match_token(vec![INTERPOLATION]) {
+                break;
+            }
+        }
+
+        let end = self.consume(STRING, "Expect end of string interpolation")?;
+        parts.push(Expr::Literal(Rc::new(Literal::new(end.literal.clone()))));
+
+        Ok(Expr::Interpolation(Rc::new(Interpolation::new(
+            start, parts,
+        ))))
+    }
+
     fn consume(&mut self, type_: TokenType, message: &'static str) -> Result<&Token, ParseError> {
         if self.check(type_) {
             return Ok(self.advance());
@@ -326,6 +1524,10 @@ impl Parser {
         }
     }
 
+    fn warning(&self, token: &Token, message: &'static str) {
+        crate::token_warning(token, message);
+    }
+
     fn match_token(&mut self, types: Vec<TokenType>) -> bool {
         for type_ in types {
             if self.check(type_) {
@@ -343,6 +1545,13 @@ impl Parser {
         self.peek().type_ == type_
     }
 
+    fn check_next(&self, type_: TokenType) -> bool {
+        match self.tokens.get(self.current as usize + 1) {
+            Some(token) => token.type_ == type_,
+            None => false,
+        }
+    }
+
     fn advance(&mut self) -> &Token {
         if !self.is_at_end() {
             self.current += 1;
@@ -362,11 +1571,18 @@ impl Parser {
                 TokenType::CLASS
                 | TokenType::FUN
                 | TokenType::VAR
+                | TokenType::LET
+                | TokenType::CONST
+                | TokenType::ENUM
+                | TokenType::TRAIT
                 | TokenType::FOR
                 | TokenType::IF
                 | TokenType::WHILE
                 | TokenType::PRINT
-                | TokenType::RETURN => return,
+                | TokenType::RETURN
+                | TokenType::YIELD
+                | TokenType::BREAK
+                | TokenType::CONTINUE => return,
                 _ => {}
             }
 
@@ -1,58 +1,96 @@
-use std::collections::HashMap;
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::UnicodeNormalization;
 
-use crate::{literal_object::Literal, token::Token, token_type::TokenType};
+use crate::{
+    big_int::BigInt, decimal::Decimal, dialect::Dialect, literal_object::Literal, token::Token,
+    token_type::TokenType,
+};
 
 pub struct Scanner {
-    pub source: String,
+    // Kept as chars so offsets line up with non-ASCII identifiers and strings.
+    pub source: Vec<char>,
     pub tokens: Vec<Token>,
     pub start: u64,
     pub current: u64,
     pub line: u64,
-    pub keywords: HashMap<String, TokenType>,
+    // Offset where the current line begins, used to compute token columns.
+    pub line_start: u64,
+    // Where the current lexeme begins. Strings can span lines, so `line`
+    // may have moved on by the time its token is added.
+    pub start_line: u64,
+    pub column: u64,
+    pub dialect: Dialect,
+    // Open `${` interpolations, each with the number of unclosed `{` inside it.
+    pub interpolations: Vec<u32>,
 }
 
-impl Scanner {
-    pub fn default(source: &str) -> Self {
-        let mut keywords = HashMap::new();
-        keywords.insert("and".to_string(), TokenType::AND);
-        keywords.insert("class".to_string(), TokenType::CLASS);
-        keywords.insert("else".to_string(), TokenType::ELSE);
-        keywords.insert("false".to_string(), TokenType::FALSE);
-        keywords.insert("for".to_string(), TokenType::FOR);
-        keywords.insert("fun".to_string(), TokenType::FUN);
-        keywords.insert("if".to_string(), TokenType::IF);
-        keywords.insert("nil".to_string(), TokenType::NIL);
-        keywords.insert("or".to_string(), TokenType::OR);
-        keywords.insert("print".to_string(), TokenType::PRINT);
-        keywords.insert("return".to_string(), TokenType::RETURN);
-        keywords.insert("super".to_string(), TokenType::SUPER);
-        keywords.insert("this".to_string(), TokenType::THIS);
-        keywords.insert("true".to_string(), TokenType::TRUE);
-        keywords.insert("var".to_string(), TokenType::VAR);
-        keywords.insert("while".to_string(), TokenType::WHILE);
+/// Scans `source` on its own, without parsing or running it. Nothing is
+/// printed: errors come back as `TokenType::ERROR` tokens whose literal is
+/// the message. Extension keywords are scanned as identifiers, see
+/// `lex_with_dialect`.
+pub fn lex(source: &str) -> impl Iterator<Item = Token> {
+    lex_with_dialect(source, Dialect::default())
+}
+
+/// Like `lex`, with the keywords of the extensions enabled in `dialect`.
+pub fn lex_with_dialect(source: &str, dialect: Dialect) -> impl Iterator<Item = Token> {
+    let mut scanner = Scanner::new(source, dialect);
+    scanner.scan_tokens();
+    scanner.tokens.into_iter()
+}
+
+// Drops a UTF-8 byte order mark and turns CRLF line endings into LF, so that
+// line counting and string literals are the same on every platform.
+fn normalize_source(source: &str) -> String {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    source.replace("\r\n", "\n")
+}
 
+impl Scanner {
+    pub fn new(source: &str, dialect: Dialect) -> Self {
         Scanner {
-            source: source.to_string(),
+            source: normalize_source(source).chars().collect(),
             tokens: vec![],
             start: 0,
             current: 0,
             line: 1,
-            keywords,
+            line_start: 0,
+            start_line: 1,
+            column: 1,
+            dialect,
+            interpolations: vec![],
         }
     }
 
     pub fn scan_tokens(&mut self) -> &Vec<Token> {
+        // Scripts may start with "#!/usr/bin/env rlox" to run as executables.
+        if self.peek() == '#' && self.peek_next() == '!' {
+            while !self.is_at_end() && self.peek() != '\n' {
+                self.advance();
+            }
+        }
+
         while !self.is_at_end() {
             // we are at the beginning of the next lexeme
             self.start = self.current;
+            self.start_line = self.line;
+            self.column = self.current - self.line_start + 1;
             self.scan_token()
         }
 
+        if !self.interpolations.is_empty() {
+            self.start = self.current;
+            self.start_line = self.line;
+            self.column = self.current - self.line_start + 1;
+            self.add_error("Unterminated string interpolation");
+        }
+
         self.tokens.push(Token::new(
             TokenType::EOF,
             "".to_string(),
             Literal::Null,
             self.line,
+            self.current - self.line_start + 1,
         ));
         return &self.tokens;
     }
@@ -62,14 +100,92 @@ impl Scanner {
         match c {
             Some('(') => self.add_token(TokenType::LEFT_PAREN),
             Some(')') => self.add_token(TokenType::RIGHT_PAREN),
-            Some('{') => self.add_token(TokenType::LEFT_BRACE),
-            Some('}') => self.add_token(TokenType::RIGHT_BRACE),
+            Some('{') => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LEFT_BRACE);
+            }
+            Some('}') => match self.interpolations.last_mut() {
+                // Closes an embedded expression, the string continues after it.
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.string();
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    self.add_token(TokenType::RIGHT_BRACE);
+                }
+                None => self.add_token(TokenType::RIGHT_BRACE),
+            },
+            Some('[') => self.add_token(TokenType::LEFT_BRACKET),
+            Some(']') => self.add_token(TokenType::RIGHT_BRACKET),
             Some(',') => self.add_token(TokenType::COMMA),
-            Some('.') => self.add_token(TokenType::DOT),
-            Some('-') => self.add_token(TokenType::MINUS),
-            Some('+') => self.add_token(TokenType::PLUS),
+            Some(':') => self.add_token(TokenType::COLON),
+            Some('.') => {
+                if !self.match_lexeme('.') {
+                    self.add_token(TokenType::DOT);
+                } else if self.match_lexeme('=') {
+                    self.add_token(TokenType::DOT_DOT_EQUAL);
+                } else if self.match_lexeme('.') {
+                    self.add_token(TokenType::ELLIPSIS);
+                } else {
+                    self.add_token(TokenType::DOT_DOT);
+                }
+            }
+            Some('-') => {
+                if self.match_lexeme('-') {
+                    self.add_token(TokenType::MINUS_MINUS);
+                } else if self.match_lexeme('=') {
+                    self.add_token(TokenType::MINUS_EQUAL);
+                } else {
+                    self.add_token(TokenType::MINUS);
+                }
+            }
+            Some('+') => {
+                if self.match_lexeme('+') {
+                    self.add_token(TokenType::PLUS_PLUS);
+                } else if self.match_lexeme('=') {
+                    self.add_token(TokenType::PLUS_EQUAL);
+                } else {
+                    self.add_token(TokenType::PLUS);
+                }
+            }
             Some(';') => self.add_token(TokenType::SEMICOLON),
-            Some('*') => self.add_token(TokenType::STAR),
+            Some('*') => {
+                if self.match_lexeme('*') {
+                    self.add_token(TokenType::STAR_STAR);
+                } else if self.match_lexeme('=') {
+                    self.add_token(TokenType::STAR_EQUAL);
+                } else {
+                    self.add_token(TokenType::STAR);
+                }
+            }
+            Some('%') => {
+                match self.match_lexeme('=') {
+                    true => self.add_token(TokenType::PERCENT_EQUAL),
+                    false => self.add_token(TokenType::PERCENT),
+                };
+            }
+            Some('&') => self.add_token(TokenType::AMPERSAND),
+            Some('|') => self.add_token(TokenType::PIPE),
+            Some('^') => self.add_token(TokenType::CARET),
+            Some('?') => {
+                if self.match_lexeme('?') {
+                    self.add_token(TokenType::QUESTION_QUESTION);
+                } else if self.match_lexeme('.') {
+                    self.add_token(TokenType::QUESTION_DOT);
+                } else {
+                    self.add_token(TokenType::QUESTION);
+                }
+            }
+            Some('~') => {
+                // `~/` is integer division, as `//` already starts a comment.
+                match self.match_lexeme('/') {
+                    true => self.add_token(TokenType::TILDE_SLASH),
+                    false => self.add_token(TokenType::TILDE),
+                };
+            }
             Some('!') => {
                 match self.match_lexeme('=') {
                     true => self.add_token(TokenType::BANG_EQUAL),
@@ -77,28 +193,39 @@ impl Scanner {
                 };
             }
             Some('=') => {
-                match self.match_lexeme('=') {
-                    true => self.add_token(TokenType::EQUAL_EQUAL),
-                    false => self.add_token(TokenType::EQUAL),
-                };
+                if self.match_lexeme('=') {
+                    self.add_token(TokenType::EQUAL_EQUAL);
+                } else if self.match_lexeme('>') {
+                    self.add_token(TokenType::ARROW);
+                } else {
+                    self.add_token(TokenType::EQUAL);
+                }
             }
             Some('<') => {
-                match self.match_lexeme('=') {
-                    true => self.add_token(TokenType::LESS_EQUAL),
-                    false => self.add_token(TokenType::LESS),
-                };
+                if self.match_lexeme('=') {
+                    self.add_token(TokenType::LESS_EQUAL);
+                } else if self.match_lexeme('<') {
+                    self.add_token(TokenType::LESS_LESS);
+                } else {
+                    self.add_token(TokenType::LESS);
+                }
             }
             Some('>') => {
-                match self.match_lexeme('=') {
-                    true => self.add_token(TokenType::GREATER_EQUAL),
-                    false => self.add_token(TokenType::GREATER),
-                };
+                if self.match_lexeme('=') {
+                    self.add_token(TokenType::GREATER_EQUAL);
+                } else if self.match_lexeme('>') {
+                    self.add_token(TokenType::GREATER_GREATER);
+                } else {
+                    self.add_token(TokenType::GREATER);
+                }
             }
             Some('/') => {
                 if self.match_lexeme('/') {
-                    while self.peek() != '\n' && self.is_at_end() {
+                    while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                } else if self.match_lexeme('=') {
+                    self.add_token(TokenType::SLASH_EQUAL);
                 } else {
                     self.add_token(TokenType::SLASH);
                 }
@@ -109,6 +236,7 @@ impl Scanner {
             Some('\n') => {
                 // Ignore whitespace.
                 self.line = self.line + 1;
+                self.line_start = self.current;
             }
             Some('"') => self.string(),
             Some(c) => {
@@ -118,10 +246,8 @@ impl Scanner {
                 } else if self.is_alpha(c) {
                     self.identifier();
                     return;
-                } else {
-                    crate::error(self.line, "Unexpected character");
                 }
-                crate::error(self.line, "Unexpected character")
+                self.add_error("Unexpected character")
             }
             None => {}
         }
@@ -131,18 +257,21 @@ impl Scanner {
         if self.is_at_end() {
             return '\n';
         }
-        return self.source.chars().nth(self.current as usize).unwrap();
+        return self.source[self.current as usize];
     }
 
     fn peek_next(&self) -> char {
         if self.current + 1 >= self.source.len() as u64 {
             return '\0';
         }
-        return self
-            .source
-            .chars()
-            .nth((self.current as usize) + 1)
-            .unwrap();
+        return self.source[(self.current as usize) + 1];
+    }
+
+    fn peek_at(&self, offset: u64) -> char {
+        if self.current + offset >= self.source.len() as u64 {
+            return '\0';
+        }
+        return self.source[(self.current + offset) as usize];
     }
 
     fn identifier(&mut self) {
@@ -152,26 +281,29 @@ impl Scanner {
         let start = self.start as usize;
         let current = self.current as usize;
 
-        let text = &self.source[start..current];
-        let type_check = self.keywords.get(text);
-        let type_;
-        match type_check {
-            Some(type_check) => type_ = type_check.clone(),
-            None => type_ = TokenType::IDENTIFIER,
-        }
-        self.add_token(type_);
+        // Compare names in NFC so that visually identical identifiers are equal.
+        let text: String = self.substring(start, current).nfc().collect();
+        let type_ = self.dialect.keyword(&text).unwrap_or(TokenType::IDENTIFIER);
+        self.tokens.push(Token::new(
+            type_,
+            text,
+            Literal::Null,
+            self.line,
+            self.column,
+        ));
     }
 
     fn is_digit(&self, c: char) -> bool {
-        c >= '0' && c < '9'
+        c.is_ascii_digit()
     }
 
+    // Identifiers follow the UAX #31 default syntax, plus a leading '_'.
     fn is_alpha(&self, c: char) -> bool {
-        return (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || c == '_';
+        c == '_' || is_xid_start(c)
     }
 
     fn is_alpha_numeric(&self, c: char) -> bool {
-        self.is_alpha(c) || self.is_digit(c)
+        is_xid_continue(c)
     }
 
     fn number(&mut self) {
@@ -180,41 +312,78 @@ impl Scanner {
         }
 
         // Look for a fractional part
+        let mut is_float = false;
         if self.peek() == '.' && self.is_digit(self.peek_next()) {
             // Consume the "."
             self.advance();
-        }
+            is_float = true;
 
-        while self.is_digit(self.peek()) {
-            self.peek();
+            while self.is_digit(self.peek()) {
+                self.advance();
+            }
         }
 
         let start = self.start as usize;
         let current = self.current as usize;
-        let number = &self.source[start..current];
-        let number: f64 = number.parse().expect("Failed to parse number");
-        self.add_token_literal(TokenType::NUMBER, Literal::Number(number));
+        let number = self.substring(start, current);
+
+        // A 'd' suffix makes an exact decimal, like 12.50d.
+        if self.peek() == 'd' && !self.is_alpha_numeric(self.peek_next()) {
+            self.advance();
+            let decimal = Decimal::parse(&number).expect("Failed to parse decimal");
+            self.add_token_literal(TokenType::NUMBER, Literal::Decimal(decimal));
+            return;
+        }
+
+        if is_float {
+            let number: f64 = number.parse().expect("Failed to parse number");
+            self.add_token_literal(TokenType::NUMBER, Literal::Number(number));
+        } else {
+            match number.parse::<i64>() {
+                Ok(number) => self.add_token_literal(TokenType::NUMBER, Literal::Integer(number)),
+                Err(_) => {
+                    let number = BigInt::parse(&number).expect("Failed to parse number");
+                    self.add_token_literal(TokenType::NUMBER, Literal::BigInt(number));
+                }
+            }
+        }
     }
 
     fn string(&mut self) {
+        let mut value = String::new();
         while self.peek() != '"' && !self.is_at_end() {
+            // "$${" is an escaped, literal "${".
+            if self.peek() == '$' && self.peek_next() == '$' && self.peek_at(2) == '{' {
+                self.advance();
+                self.advance();
+                self.advance();
+                value.push_str("${");
+                continue;
+            }
+            if self.peek() == '$' && self.peek_next() == '{' {
+                // Consume the "${"
+                self.advance();
+                self.advance();
+                self.add_token_literal(TokenType::INTERPOLATION, Literal::String(value));
+                self.interpolations.push(0);
+                return;
+            }
             if self.peek() == '\n' {
                 self.line = self.line + 1;
+                self.line_start = self.current + 1;
+            }
+            if let Some(c) = self.advance() {
+                value.push(c);
             }
-            self.advance();
         }
 
         if self.is_at_end() {
-            crate::error(self.line, "Unterminated string");
+            self.add_error("Unterminated string");
             return;
         }
 
         self.advance();
-
-        let start = self.start as usize + 1;
-        let current = self.current as usize - 1;
-        let value = &self.source[start..current];
-        self.add_token_literal(TokenType::STRING, Literal::String(value.to_string()));
+        self.add_token_literal(TokenType::STRING, Literal::String(value));
     }
 
     fn match_lexeme(&mut self, expected: char) -> bool {
@@ -222,7 +391,7 @@ impl Scanner {
             return false;
         }
 
-        if self.source.chars().nth(self.current as usize).unwrap() != expected {
+        if self.source[self.current as usize] != expected {
             return false;
         }
         self.current = self.current + 1;
@@ -230,7 +399,7 @@ impl Scanner {
     }
 
     fn advance(&mut self) -> Option<char> {
-        let c = self.source.chars().nth(self.current as usize);
+        let c = self.source.get(self.current as usize).copied();
         self.current = self.current + 1;
         return c;
     }
@@ -243,9 +412,23 @@ impl Scanner {
         let start = self.start as usize;
         let current = self.current as usize;
 
-        let text = self.source[start..current].to_string().clone();
-        self.tokens
-            .push(Token::new(type_, text, literal, self.line));
+        let text = self.substring(start, current);
+        self.tokens.push(Token::new(
+            type_,
+            text,
+            literal,
+            self.start_line,
+            self.column,
+        ));
+    }
+
+    // Adds the current lexeme as an ERROR token carrying `message`.
+    fn add_error(&mut self, message: &str) {
+        self.add_token_literal(TokenType::ERROR, Literal::String(message.to_string()));
+    }
+
+    fn substring(&self, start: usize, end: usize) -> String {
+        self.source[start..end].iter().collect()
     }
 
     fn is_at_end(&self) -> bool {
@@ -1,13 +1,25 @@
 use std::rc::Rc;
 
-use crate::{expr::Expr, token::Token};
+use crate::{
+    expr::{Expr, Lambda, Pattern},
+    token::Token,
+};
 
 #[derive(Clone, Debug)]
 pub enum Stmt {
     Block(Rc<Block>),
+    Break(Rc<Break>),
+    Class(Rc<Class>),
+    Continue(Rc<Continue>),
+    Enum(Rc<Enum>),
     Expression(Rc<Expression>),
+    ForIn(Rc<ForIn>),
+    Function(Rc<Function>),
     Print(Rc<Print>),
+    Return(Rc<Return>),
+    Trait(Rc<Trait>),
     Var(Rc<Var>),
+    Yield(Rc<Yield>),
 }
 
 pub trait Accept {
@@ -16,9 +28,36 @@ pub trait Accept {
 
 pub trait Visitor<R> {
     fn visit_block_stmt(&mut self, stmt: &Block) -> R;
+    fn visit_break_stmt(&mut self, stmt: &Break) -> R;
+    fn visit_class_stmt(&mut self, stmt: &Class) -> R;
+    fn visit_continue_stmt(&mut self, stmt: &Continue) -> R;
+    fn visit_enum_stmt(&mut self, stmt: &Enum) -> R;
     fn visit_expression_stmt(&mut self, stmt: &Expression) -> R;
+    fn visit_for_in_stmt(&mut self, stmt: &ForIn) -> R;
+    fn visit_function_stmt(&mut self, stmt: &Function) -> R;
     fn visit_print_stmt(&mut self, stmt: &Print) -> R;
+    fn visit_return_stmt(&mut self, stmt: &Return) -> R;
+    fn visit_trait_stmt(&mut self, stmt: &Trait) -> R;
     fn visit_var_stmt(&mut self, stmt: &Var) -> R;
+    fn visit_yield_stmt(&mut self, stmt: &Yield) -> R;
+}
+
+#[derive(Debug)]
+pub struct Class {
+    name: Token,
+    superclass: Option<Token>,
+    // The traits named after `with`, in order.
+    traits: Vec<Token>,
+    methods: Vec<Rc<Function>>,
+    getters: Vec<Rc<Function>>,
+    setters: Vec<Rc<Function>>,
+    statics: Vec<Rc<Function>>,
+}
+
+#[derive(Debug)]
+pub struct Enum {
+    name: Token,
+    variants: Vec<Variant>,
 }
 
 #[derive(Debug)]
@@ -26,14 +65,46 @@ pub struct Expression {
     pub expression: Expr,
 }
 
+#[derive(Debug)]
+pub struct ForIn {
+    label: Option<Token>,
+    pattern: Pattern,
+    iterable: Expr,
+    body: Stmt,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    name: Token,
+    lambda: Rc<Lambda>,
+}
+
 #[derive(Debug)]
 pub struct Print {
+    keyword: Token,
     expression: Expr,
 }
 
 #[derive(Debug)]
-pub struct Var {
+pub struct Return {
+    keyword: Token,
+    value: Option<Expr>,
+}
+
+#[derive(Debug)]
+pub struct Trait {
     name: Token,
+    // Methods with a default body.
+    methods: Vec<Rc<Function>>,
+    // Names of the methods a class has to define itself.
+    required: Vec<Token>,
+}
+
+#[derive(Debug)]
+pub struct Var {
+    // The `var`, `let` or `const` that declared it.
+    keyword: Token,
+    pattern: Pattern,
     initializer: Expr,
 }
 
@@ -42,18 +113,64 @@ pub struct Block {
     statements: Vec<Stmt>,
 }
 
+#[derive(Debug)]
+pub struct Break {
+    label: Option<Token>,
+}
+
+#[derive(Debug)]
+pub struct Continue {
+    label: Option<Token>,
+}
+
+impl Accept for Class {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_class_stmt(self)
+    }
+}
+
+impl Accept for Enum {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_enum_stmt(self)
+    }
+}
+
 impl Accept for Expression {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_expression_stmt(self)
     }
 }
 
+impl Accept for ForIn {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_for_in_stmt(self)
+    }
+}
+
+impl Accept for Function {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_function_stmt(self)
+    }
+}
+
 impl Accept for Print {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_print_stmt(self)
     }
 }
 
+impl Accept for Return {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_return_stmt(self)
+    }
+}
+
+impl Accept for Trait {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_trait_stmt(self)
+    }
+}
+
 impl Accept for Var {
     fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
         visitor.visit_var_stmt(self)
@@ -66,6 +183,137 @@ impl Accept for Block {
     }
 }
 
+impl Accept for Break {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_break_stmt(self)
+    }
+}
+
+impl Accept for Continue {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_continue_stmt(self)
+    }
+}
+
+impl Accept for Yield {
+    fn accept<R>(&self, visitor: &mut dyn Visitor<R>) -> R {
+        visitor.visit_yield_stmt(self)
+    }
+}
+
+impl Class {
+    pub fn new(
+        name: Token,
+        superclass: Option<Token>,
+        traits: Vec<Token>,
+        methods: Vec<Rc<Function>>,
+        getters: Vec<Rc<Function>>,
+        setters: Vec<Rc<Function>>,
+        statics: Vec<Rc<Function>>,
+    ) -> Self {
+        Class {
+            name,
+            superclass,
+            traits,
+            methods,
+            getters,
+            setters,
+            statics,
+        }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn superclass(&self) -> &Option<Token> {
+        &self.superclass
+    }
+
+    pub fn traits(&self) -> &Vec<Token> {
+        &self.traits
+    }
+
+    pub fn methods(&self) -> &Vec<Rc<Function>> {
+        &self.methods
+    }
+
+    pub fn getters(&self) -> &Vec<Rc<Function>> {
+        &self.getters
+    }
+
+    pub fn setters(&self) -> &Vec<Rc<Function>> {
+        &self.setters
+    }
+
+    pub fn statics(&self) -> &Vec<Rc<Function>> {
+        &self.statics
+    }
+}
+
+impl Enum {
+    pub fn new(name: Token, variants: Vec<Variant>) -> Self {
+        Enum { name, variants }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn variants(&self) -> &Vec<Variant> {
+        &self.variants
+    }
+}
+
+/// One variant of an `enum` declaration, like `Custom(r, g, b)`.
+#[derive(Clone, Debug)]
+pub struct Variant {
+    name: Token,
+    fields: Vec<Token>,
+}
+
+#[derive(Debug)]
+pub struct Yield {
+    keyword: Token,
+    value: Option<Expr>,
+}
+
+impl Trait {
+    pub fn new(name: Token, methods: Vec<Rc<Function>>, required: Vec<Token>) -> Self {
+        Trait {
+            name,
+            methods,
+            required,
+        }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &Vec<Rc<Function>> {
+        &self.methods
+    }
+
+    pub fn required(&self) -> &Vec<Token> {
+        &self.required
+    }
+}
+
+impl Variant {
+    pub fn new(name: Token, fields: Vec<Token>) -> Self {
+        Variant { name, fields }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &Vec<Token> {
+        &self.fields
+    }
+}
+
 impl Expression {
     pub fn new(expression: Expr) -> Self {
         Expression { expression }
@@ -76,9 +324,57 @@ impl Expression {
     }
 }
 
+impl ForIn {
+    pub fn new(label: Option<Token>, pattern: Pattern, iterable: Expr, body: Stmt) -> Self {
+        ForIn {
+            label,
+            pattern,
+            iterable,
+            body,
+        }
+    }
+
+    pub fn label(&self) -> &Option<Token> {
+        &self.label
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub fn iterable(&self) -> &Expr {
+        &self.iterable
+    }
+
+    pub fn body(&self) -> &Stmt {
+        &self.body
+    }
+}
+
+impl Function {
+    pub fn new(name: Token, lambda: Rc<Lambda>) -> Self {
+        Function { name, lambda }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn lambda(&self) -> &Rc<Lambda> {
+        &self.lambda
+    }
+}
+
 impl Print {
-    pub fn new(expression: Expr) -> Self {
-        Print { expression }
+    pub fn new(keyword: Token, expression: Expr) -> Self {
+        Print {
+            keyword,
+            expression,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
     }
 
     pub fn expression(&self) -> &Expr {
@@ -86,13 +382,35 @@ impl Print {
     }
 }
 
+impl Return {
+    pub fn new(keyword: Token, value: Option<Expr>) -> Self {
+        Return { keyword, value }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn value(&self) -> &Option<Expr> {
+        &self.value
+    }
+}
+
 impl Var {
-    pub fn new(name: Token, initializer: Expr) -> Self {
-        Var { name, initializer }
+    pub fn new(keyword: Token, pattern: Pattern, initializer: Expr) -> Self {
+        Var {
+            keyword,
+            pattern,
+            initializer,
+        }
     }
 
-    pub fn name(&self) -> &Token {
-        &self.name
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
     }
 
     pub fn initializer(&self) -> &Expr {
@@ -109,3 +427,37 @@ impl Block {
         &self.statements
     }
 }
+
+impl Break {
+    pub fn new(label: Option<Token>) -> Self {
+        Break { label }
+    }
+
+    pub fn label(&self) -> &Option<Token> {
+        &self.label
+    }
+}
+
+impl Continue {
+    pub fn new(label: Option<Token>) -> Self {
+        Continue { label }
+    }
+
+    pub fn label(&self) -> &Option<Token> {
+        &self.label
+    }
+}
+
+impl Yield {
+    pub fn new(keyword: Token, value: Option<Expr>) -> Self {
+        Yield { keyword, value }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn value(&self) -> &Option<Expr> {
+        &self.value
+    }
+}
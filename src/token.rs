@@ -6,19 +6,32 @@ pub struct Token {
     pub lexeme: String,
     pub literal: Literal,
     pub line: u64,
+    pub column: u64,
 }
 
 impl Token {
-    pub fn new(type_: TokenType, lexeme: String, literal: Literal, line: u64) -> Self {
+    pub fn new(type_: TokenType, lexeme: String, literal: Literal, line: u64, column: u64) -> Self {
         Token {
             type_,
             lexeme,
             literal,
             line,
+            column,
+        }
+    }
+
+    /// The message of an `ERROR` token, None for any other token.
+    pub fn error_message(&self) -> Option<&str> {
+        match (self.type_, &self.literal) {
+            (TokenType::ERROR, Literal::String(message)) => Some(message),
+            _ => None,
         }
     }
 
     pub fn to_string(&self) -> String {
-        return format!("{:?} {} {:?}", self.type_, self.lexeme, self.literal);
+        return format!(
+            "{:?} {} {:?} {}:{}",
+            self.type_, self.lexeme, self.literal, self.line, self.column
+        );
     }
 }
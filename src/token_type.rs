@@ -5,13 +5,21 @@ pub enum TokenType {
     RIGHT_PAREN,
     LEFT_BRACE,
     RIGHT_BRACE,
+    LEFT_BRACKET,
+    RIGHT_BRACKET,
     COMMA,
+    COLON,
     DOT,
     MINUS,
     PLUS,
     SEMICOLON,
     SLASH,
     STAR,
+    PERCENT,
+    AMPERSAND,
+    PIPE,
+    CARET,
+    QUESTION,
 
     // One or two character tokens.
     BANG,
@@ -22,10 +30,30 @@ pub enum TokenType {
     GREATER_EQUAL,
     LESS,
     LESS_EQUAL,
+    LESS_LESS,
+    GREATER_GREATER,
+    STAR_STAR,
+    PLUS_EQUAL,
+    MINUS_EQUAL,
+    STAR_EQUAL,
+    SLASH_EQUAL,
+    PERCENT_EQUAL,
+    ARROW,
+    QUESTION_QUESTION,
+    QUESTION_DOT,
+    PLUS_PLUS,
+    MINUS_MINUS,
+    TILDE,
+    TILDE_SLASH,
+    DOT_DOT,
+    DOT_DOT_EQUAL,
+    ELLIPSIS,
 
     // Literals.
     IDENTIFIER,
     STRING,
+    // A string segment that is followed by an embedded `${` expression.
+    INTERPOLATION,
     NUMBER,
 
     // Keywords.
@@ -46,5 +74,19 @@ pub enum TokenType {
     VAR,
     WHILE,
 
+    // Extension keywords, only reserved when enabled in the `Dialect`.
+    BREAK,
+    CONTINUE,
+    LET,
+    CONST,
+    MATCH,
+    IMPORT,
+    ENUM,
+    YIELD,
+    TRAIT,
+
+    // Source the scanner couldn't make sense of. The literal holds the
+    // message, and the parser reports it.
+    ERROR,
     EOF,
 }
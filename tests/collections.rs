@@ -0,0 +1,53 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn lists_containing_themselves_compare_and_print() {
+    let output = run(
+        "",
+        r#"
+        var xs = [1];
+        xs.push(xs);
+        var ys = [1];
+        ys.push(ys);
+        var zs = [2];
+        zs.push(zs);
+        print xs == xs;
+        print xs == ys;
+        print xs == zs;
+        print xs;
+        print [xs, (xs,)];
+        "#,
+    );
+    assert_eq!(
+        output,
+        "true\ntrue\nfalse\n[1, [...]]\n[[1, [...]], ([1, [...]],)]\n"
+    );
+}
+
+#[test]
+fn maps_containing_themselves_compare_and_print() {
+    let output = run(
+        "",
+        r#"
+        var m = {"a": 1};
+        m["self"] = m;
+        var n = {"a": 1};
+        n["self"] = n;
+        var o = {"a": 2};
+        o["self"] = o;
+        print m == m;
+        print m == n;
+        print m == o;
+        print m;
+        var xs = [m];
+        m["list"] = xs;
+        print xs;
+        "#,
+    );
+    assert_eq!(
+        output,
+        "true\ntrue\nfalse\n{a: 1, self: {...}}\n[{a: 1, self: {...}, list: [...]}]\n"
+    );
+}
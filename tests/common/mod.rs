@@ -0,0 +1,33 @@
+use std::{
+    env, fs,
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static SCRIPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs `source` as a script with the given `--enable` extensions and
+/// returns what it printed. A runtime error is added as a last
+/// `error: <message>` line.
+pub fn run(extensions: &str, source: &str) -> String {
+    let number = SCRIPTS.fetch_add(1, Ordering::Relaxed);
+    let path = env::temp_dir().join(format!("rlox-{}-{}.lox", std::process::id(), number));
+    fs::write(&path, source).expect("the script can be written");
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_interpreter"));
+    if !extensions.is_empty() {
+        command.arg(format!("--enable={}", extensions));
+    }
+    let output = command.arg(&path).output().expect("the interpreter runs");
+    fs::remove_file(&path).ok();
+
+    let mut printed = String::from_utf8(output.stdout).expect("the output is UTF-8");
+    let errors = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    let mut lines = errors.lines();
+    if lines.any(|line| line.contains("panicked at")) {
+        if let Some(message) = lines.next() {
+            printed.push_str(&format!("error: {}\n", message));
+        }
+    }
+    printed
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn yield_inside_nested_for_in() {
+    let output = run(
+        "yield",
+        r#"
+        fun pairs() {
+            for (i in 1..=2) {
+                for (j in ["a", "b"]) {
+                    yield "${i}${j}";
+                }
+            }
+        }
+        for (p in pairs()) print p;
+        "#,
+    );
+    assert_eq!(output, "1a\n1b\n2a\n2b\n");
+}
+
+#[test]
+fn labeled_break_and_continue_leave_suspended_loops() {
+    let output = run(
+        "yield,break,continue",
+        r#"
+        fun walk() {
+            outer: for (i in 0..3) {
+                for (j in 0..3) {
+                    yield [i, j];
+                    continue outer;
+                }
+            }
+            outer: for (i in 0..3) {
+                for (j in 0..3) {
+                    { yield [i, j]; break outer; }
+                }
+            }
+            yield "done";
+        }
+        for (step in walk()) print step;
+        "#,
+    );
+    assert_eq!(output, "[0, 0]\n[1, 0]\n[2, 0]\n[0, 0]\ndone\n");
+}
+
+#[test]
+fn return_finishes_a_generator() {
+    let output = run(
+        "yield",
+        r#"
+        fun early() {
+            for (i in 0..10) {
+                yield i;
+                return;
+            }
+            yield "unreachable";
+        }
+        var g = early();
+        print g.next();
+        print g.next();
+        for (x in early()) print x;
+        "#,
+    );
+    assert_eq!(output, "0\nnil\n0\n");
+}
+
+#[test]
+fn next_after_the_end_keeps_giving_nil() {
+    let output = run(
+        "yield",
+        r#"
+        fun once() { yield 1; }
+        var g = once();
+        for (x in g) print x;
+        print g.next();
+        print g.next();
+        "#,
+    );
+    assert_eq!(output, "1\nnil\nnil\n");
+}
+
+#[test]
+fn a_generator_cannot_resume_itself() {
+    let output = run(
+        "yield",
+        r#"
+        var g;
+        fun selfish() { yield g.next(); }
+        g = selfish();
+        print g.next();
+        "#,
+    );
+    assert_eq!(output, "error: Generator is already running\n");
+}
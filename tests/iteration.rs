@@ -0,0 +1,58 @@
+mod common;
+
+use common::run;
+
+const COUNTDOWN: &str = r#"
+class Countdown {
+    init(n) { this.n = n; }
+    iter() { return CountdownIterator(this.n); }
+}
+class CountdownIterator {
+    init(n) { this.n = n; }
+    next() {
+        var n = this.n;
+        this.n = n - 1;
+        return match (n) { 0 => nil, _ => n };
+    }
+}
+"#;
+
+#[test]
+fn instances_iterate_through_iter_and_next() {
+    let source = format!("{}{}", COUNTDOWN, "for (n in Countdown(3)) print n;\n");
+    assert_eq!(run("match", &source), "3\n2\n1\n");
+}
+
+#[test]
+fn iter_can_return_a_list_or_a_generator() {
+    let output = run(
+        "yield",
+        r#"
+        fun evens(n) { for (i in 0..n) yield i * 2; }
+        class Listed { iter() { return [1, 2]; } }
+        class Generated { iter() { return evens(3); } }
+        for (x in Listed()) print x;
+        for (x in Generated()) print x;
+        "#,
+    );
+    assert_eq!(output, "1\n2\n0\n2\n4\n");
+}
+
+#[test]
+fn generators_can_loop_over_instances() {
+    let source = format!(
+        "{}{}",
+        COUNTDOWN,
+        "fun doubled() { for (n in Countdown(2)) yield n * 2; }\nfor (n in doubled()) print n;\n"
+    );
+    assert_eq!(run("match,yield", &source), "4\n2\n");
+}
+
+#[test]
+fn instances_without_iter_are_errors() {
+    let output = run("", "class Plain {}\nfor (x in Plain()) print x;\n");
+    assert_eq!(
+        output,
+        "error: Can only iterate over instances with an 'iter' method\n"
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn labels_pick_the_loop_to_leave() {
+    let output = run(
+        "break,continue",
+        r#"
+        outer: for (i in 0..3) {
+            for (j in 0..3) {
+                print [i, j];
+                continue outer;
+            }
+        }
+        outer: for (i in 0..3) {
+            for (j in 0..3) {
+                print [i, j];
+                break outer;
+            }
+        }
+        for (i in 0..2) {
+            inner: for (j in 0..3) {
+                break inner;
+            }
+            print i;
+        }
+        "#,
+    );
+    assert_eq!(output, "[0, 0]\n[1, 0]\n[2, 0]\n[0, 0]\n0\n1\n");
+}
+
+#[test]
+fn leaving_a_loop_restores_the_enclosing_scope() {
+    let output = run(
+        "break,continue",
+        r#"
+        var x = "global";
+        outer: for (i in 0..2) {
+            var x = "loop";
+            for (j in 0..2) {
+                var x = "inner";
+                { var x = "block"; break outer; }
+            }
+        }
+        print x;
+        for (i in 0..2) {
+            var x = "loop";
+            { var x = "block"; continue; }
+        }
+        print x;
+        "#,
+    );
+    assert_eq!(output, "global\nglobal\n");
+}
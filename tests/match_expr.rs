@@ -0,0 +1,92 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn parenthesized_guard_is_not_an_arrow_function() {
+    let output = run(
+        "match",
+        r#"
+        fun sign(n) {
+            return match (n) { x if (x > 0) => "pos", x if (x < 0) => "neg", _ => "zero" };
+        }
+        print sign(3);
+        print sign(-3);
+        print sign(0);
+        "#,
+    );
+    assert_eq!(output, "pos\nneg\nzero\n");
+}
+
+#[test]
+fn guard_can_contain_arrow_functions() {
+    let output = run(
+        "match",
+        r#"
+        var apply = (f, x) => f(x);
+        print match (2) { n if apply((x) => x > 1, n) => "big", _ => "small" };
+        "#,
+    );
+    assert_eq!(output, "big\n");
+}
+
+#[test]
+fn class_patterns_match_instances_and_their_fields() {
+    let output = run(
+        "match",
+        r#"
+        class Point { init(x, y) { this.x = x; this.y = y; } }
+        class Point3 < Point { init(x, y, z) { super.init(x, y); this.z = z; } }
+        fun describe(p) {
+            return match (p) {
+                Point{x: 0, y: 0} => "origin",
+                Point3{x, z} => "3d ${x} ${z}",
+                Point{x, y} if x == y => "diagonal ${x}",
+                Point{x, missing} => "unreachable",
+                Point{x, y} => "point ${x} ${y}",
+                _ => "not a point"
+            };
+        }
+        print describe(Point(0, 0));
+        print describe(Point3(1, 2, 3));
+        print describe(Point(2, 2));
+        print describe(Point(1, 2));
+        print describe([1, 2]);
+        "#,
+    );
+    assert_eq!(
+        output,
+        "origin\n3d 1 3\ndiagonal 2\npoint 1 2\nnot a point\n"
+    );
+}
+
+#[test]
+fn arms_match_literals_lists_and_maps() {
+    let output = run(
+        "match",
+        r#"
+        fun describe(v) {
+            return match (v) {
+                1 | 2 => "small",
+                [x, y] => "pair ${x + y}",
+                [first, ...rest] => "list ${first} ${rest}",
+                {name} => "named ${name}",
+                nil => "nothing",
+                _ => "other"
+            };
+        }
+        print describe(2);
+        print describe([3, 4]);
+        print describe([1, 2, 3]);
+        print describe({"name": "x", "age": 3});
+        print describe(nil);
+        print describe("s");
+        print match (3) { 1 => "one" };
+        "#,
+    );
+    assert_eq!(
+        output,
+        "[line   18  ] Warning at 'match'  : Match has no '_' arm, so a value may match no arm\n\
+         small\npair 7\nlist 1 [2, 3]\nnamed x\nnothing\nother\nerror: No match arm for 3\n"
+    );
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn decimal_scales_are_capped() {
+    let output = run(
+        "",
+        "print 1.25d.round(10000).scale();\nprint 1.5d.round(4000000000);\n",
+    );
+    assert_eq!(output, "10000\nerror: Scale can be at most 10000\n");
+}
+
+#[test]
+fn huge_powers_and_shifts_are_errors() {
+    let output = run(
+        "",
+        r#"
+        print 1 ** 4000000000;
+        print 0 << 4000000000;
+        print (-3 << 100) >> 100;
+        print 2 ** 4000000000;
+        "#,
+    );
+    assert_eq!(output, "1\n0\n-3\nerror: Exponent is too large\n");
+    assert_eq!(
+        run("", "print 1 << 4000000000;\n"),
+        "error: Shift count is too large\n"
+    );
+    assert_eq!(
+        run("", "print 1.5d ** 4000000000;\n"),
+        "error: Exponent is too large\n"
+    );
+}
+
+#[test]
+fn whole_floats_keep_their_point() {
+    let output = run(
+        "",
+        "print 3.0;\nprint 10000000000000000.0;\nprint -100000000000.0 * 1000000000.0;\nprint 10000000000000000;\n",
+    );
+    assert_eq!(
+        output,
+        "3.0\n10000000000000000.0\n-100000000000000000000.0\n10000000000000000\n"
+    );
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::run;
+
+const MONEY: &str = r#"
+class Money {
+    init(cents) { this.cents = cents; }
+    cents_of(other) { return match (other) { Money{cents} => cents, _ => other * 100 }; }
+    __lt__(other) { return this.cents < this.cents_of(other); }
+    __eq__(other) { return this.cents == this.cents_of(other); }
+    __add__(other) { return Money(this.cents + this.cents_of(other)); }
+    __radd__(other) { return Money(this.cents_of(other) + this.cents); }
+    __str__() { return "${this.cents}c"; }
+}
+var m = Money(700);
+"#;
+
+#[test]
+fn orderings_come_from_lt_on_either_operand() {
+    let source = format!(
+        "{}{}",
+        MONEY,
+        r#"
+        print m > 5;
+        print m <= 7;
+        print m >= 8;
+        print 5 < m;
+        print 7 >= m;
+        print 8 <= m;
+        "#
+    );
+    assert_eq!(
+        run("match", &source),
+        "true\ntrue\nfalse\ntrue\ntrue\nfalse\n"
+    );
+}
+
+#[test]
+fn equality_uses_eq_on_either_operand() {
+    let source = format!(
+        "{}{}",
+        MONEY,
+        r#"
+        print m == 7;
+        print 7 == m;
+        print m != Money(700);
+        "#
+    );
+    assert_eq!(run("match", &source), "true\ntrue\nfalse\n");
+}
+
+#[test]
+fn right_hand_instances_use_reflected_methods() {
+    let source = format!(
+        "{}{}",
+        MONEY,
+        r#"
+        print m + 1;
+        print 2 + m;
+        print [m, 3 + m];
+        "#
+    );
+    assert_eq!(run("match", &source), "800c\n900c\n[700c, 1000c]\n");
+}
+
+#[test]
+fn missing_operator_methods_are_errors() {
+    let source = format!(
+        "{}{}",
+        MONEY, "print \"before\";\nprint 2 * m;\nprint \"after\";\n"
+    );
+    assert_eq!(
+        run("match", &source),
+        "before\nerror: Class 'Money' doesn't define '__rmul__' for '*'\n"
+    );
+}
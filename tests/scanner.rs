@@ -0,0 +1,98 @@
+use rlox::{lex, TokenType};
+
+// Each token's type with the line and column it starts at.
+fn positions(source: &str) -> Vec<(TokenType, u64, u64)> {
+    lex(source)
+        .map(|token| (token.type_, token.line, token.column))
+        .collect()
+}
+
+#[test]
+fn multi_line_strings_start_where_they_open() {
+    assert_eq!(
+        positions("print \"a\nb\";"),
+        vec![
+            (TokenType::PRINT, 1, 1),
+            (TokenType::STRING, 1, 7),
+            (TokenType::SEMICOLON, 2, 3),
+            (TokenType::EOF, 2, 4),
+        ]
+    );
+}
+
+#[test]
+fn interpolation_parts_start_where_they_open() {
+    assert_eq!(
+        positions("\"a${x}\nb${\ny}c\""),
+        vec![
+            (TokenType::INTERPOLATION, 1, 1),
+            (TokenType::IDENTIFIER, 1, 5),
+            (TokenType::INTERPOLATION, 1, 6),
+            (TokenType::IDENTIFIER, 3, 1),
+            (TokenType::STRING, 3, 2),
+            (TokenType::EOF, 3, 5),
+        ]
+    );
+}
+
+#[test]
+fn errors_come_back_as_tokens() {
+    let errors: Vec<(String, u64, String)> = lex("var a = 1 @ 2;\nprint \"open\n")
+        .filter_map(|token| {
+            let message = token.error_message()?.to_string();
+            Some((token.lexeme, token.line, message))
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            ("@".to_string(), 1, "Unexpected character".to_string()),
+            ("\"open\n".to_string(), 2, "Unterminated string".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn extension_keywords_need_a_dialect() {
+    let mut dialect = rlox::Dialect::default();
+    dialect.enable(rlox::Extension::Let);
+    assert_eq!(
+        lex("let").next().map(|token| token.type_),
+        Some(TokenType::IDENTIFIER)
+    );
+    assert_eq!(
+        rlox::lex_with_dialect("let", dialect)
+            .next()
+            .map(|token| token.type_),
+        Some(TokenType::LET)
+    );
+}
+
+#[test]
+fn interpolations_can_hold_strings_and_braces() {
+    let types: Vec<TokenType> = lex("\"a${ {\"k\": \"${b}\"} }c\"")
+        .map(|token| token.type_)
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::INTERPOLATION,
+            TokenType::LEFT_BRACE,
+            TokenType::STRING,
+            TokenType::COLON,
+            TokenType::INTERPOLATION,
+            TokenType::IDENTIFIER,
+            TokenType::STRING,
+            TokenType::RIGHT_BRACE,
+            TokenType::STRING,
+            TokenType::EOF,
+        ]
+    );
+    let errors: Vec<String> = lex("\"a${b")
+        .filter_map(|token| token.error_message().map(String::from))
+        .collect();
+    assert_eq!(
+        errors,
+        vec!["Unterminated string interpolation".to_string()]
+    );
+}